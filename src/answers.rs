@@ -0,0 +1,257 @@
+use crate::error::{column, parse_number};
+use crate::{Error, Part, Result};
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Environment variable naming the answers file to verify against.
+pub const ANSWERS_VAR: &str = "AOC_ANSWERS";
+
+/// Recorded answers for our real inputs, read from a small subset of TOML:
+///
+/// ```toml
+/// # comments and blank lines are ignored
+/// [day1]
+/// part1 = "181044"
+/// part2 = 82660352
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers(BTreeMap<(u8, u8), String>);
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        crate::source::read_input(path)?.parse()
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.0.get(&(day, part.number())).map(|a| a.as_str())
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, answer: impl Into<String>) {
+        self.0.insert((day, part.number()), answer.into());
+    }
+
+    /// Compares what a solver produced with the recorded answer.
+    pub fn check(&self, day: u8, part: Part, answer: Option<&str>) -> Outcome {
+        match (self.get(day, part), answer) {
+            (None, _) => Outcome::Missing,
+            (Some(expected), Some(answer)) if expected == answer => Outcome::Pass,
+            (Some(expected), answer) => Outcome::Fail {
+                expected: expected.to_owned(),
+                found: answer.map(|a| a.to_owned()),
+            },
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let mut answers = Self::default();
+        let mut day = None;
+
+        for (i, line) in text.lines().enumerate() {
+            let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let result = if line.starts_with('[') {
+                parse_table(line).map(|d| day = Some(d))
+            } else {
+                parse_entry(line, day).map(|(day, part, answer)| {
+                    answers.0.insert((day, part), answer);
+                })
+            };
+            result.map_err(|e| e.offset(i, indent))?;
+        }
+
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut day = None;
+        for ((d, part), answer) in &self.0 {
+            if day != Some(*d) {
+                if day.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[day{}]", d)?;
+                day = Some(*d);
+            }
+            writeln!(f, "part{} = {:?}", part, answer)?;
+        }
+        Ok(())
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Parses `[day<N>]`.
+fn parse_table(line: &str) -> Result<u8> {
+    let name = line
+        .strip_prefix('[')
+        .and_then(|l| l.strip_suffix(']'))
+        .ok_or_else(|| Error::parse(1, "unterminated table header"))?;
+    let number = name
+        .strip_prefix("day")
+        .ok_or_else(|| Error::unexpected(2, name, "day<N>"))?;
+    let day = parse_number(number, 5)?;
+    if !(1..=25).contains(&day) {
+        return Err(Error::parse(5, format!("no day {}", day)));
+    }
+    Ok(day)
+}
+
+/// Parses `part<N> = "answer"` or `part<N> = 123` inside a `[day<N>]` table.
+fn parse_entry(line: &str, day: Option<u8>) -> Result<(u8, u8, String)> {
+    let day = day.ok_or_else(|| Error::parse(1, "answer outside a [day<N>] table"))?;
+    let equals = line
+        .find('=')
+        .ok_or_else(|| Error::parse(1, "expected key = value"))?;
+    let key = line[..equals].trim_end();
+    let part = match key {
+        "part1" => 1,
+        "part2" => 2,
+        key => return Err(Error::unexpected(1, key, "part1 or part2")),
+    };
+
+    let value = &line[equals + 1..];
+    let column = column(line, equals + 1 + value.len() - value.trim_start().len());
+    let value = value.trim();
+    let answer = if let Some(quoted) = value.strip_prefix('"') {
+        quoted
+            .strip_suffix('"')
+            .filter(|a| !a.contains('"'))
+            .ok_or_else(|| Error::parse(column, "unterminated string"))?
+            .to_owned()
+    } else if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        value.to_owned()
+    } else {
+        return Err(Error::unexpected(column, value, "a string or integer"));
+    };
+
+    Ok((day, part, answer))
+}
+
+/// The result of checking one part against its recorded answer.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        found: Option<String>,
+    },
+    /// There is no recorded answer to check against.
+    Missing,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail {
+                expected,
+                found: Some(found),
+            } => write!(f, "fail: expected {}, got {}", expected, found),
+            Self::Fail {
+                expected,
+                found: None,
+            } => write!(f, "fail: expected {}, got no answer", expected),
+            Self::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "# answers for our inputs
+[day1]
+part1 = \"181044\"
+part2 = 82660352 # trailing comment
+
+  [day10]
+  part1 = \"a # b\"
+";
+        let answers: Answers = text.parse().unwrap();
+        assert_eq!(answers.get(1, Part::One), Some("181044"));
+        assert_eq!(answers.get(1, Part::Two), Some("82660352"));
+        assert_eq!(answers.get(10, Part::One), Some("a # b"));
+        assert_eq!(answers.get(10, Part::Two), None);
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let mut answers = Answers::default();
+        answers.insert(3, Part::Two, "2431272960");
+        answers.insert(1, Part::One, "181044");
+        answers.insert(3, Part::One, "184");
+
+        let text = answers.to_string();
+        assert_eq!(
+            text,
+            "[day1]\npart1 = \"181044\"\n\n[day3]\npart1 = \"184\"\npart2 = \"2431272960\"\n"
+        );
+        assert_eq!(text.parse::<Answers>().unwrap(), answers);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "part1 = 1".parse::<Answers>().unwrap_err().to_string(),
+            "line 1, column 1: answer outside a [day<N>] table"
+        );
+        assert_eq!(
+            "[day1]\npart3 = 1"
+                .parse::<Answers>()
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: expected part1 or part2, found \"part3\""
+        );
+        assert_eq!(
+            "[day1]\n  part1 = abc"
+                .parse::<Answers>()
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 11: expected a string or integer, found \"abc\""
+        );
+        assert!("[day26]".parse::<Answers>().is_err());
+        assert!("[day1".parse::<Answers>().is_err());
+        assert!("[day1]\npart1 = \"1".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let answers: Answers = "[day1]\npart1 = 5".parse().unwrap();
+        assert_eq!(answers.check(1, Part::One, Some("5")), Outcome::Pass);
+        assert_eq!(
+            answers.check(1, Part::One, Some("6")).to_string(),
+            "fail: expected 5, got 6"
+        );
+        assert_eq!(
+            answers.check(1, Part::One, None).to_string(),
+            "fail: expected 5, got no answer"
+        );
+        assert_eq!(answers.check(1, Part::Two, Some("5")), Outcome::Missing);
+    }
+}
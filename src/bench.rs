@@ -0,0 +1,230 @@
+use crate::output::{json_array, json_object, json_string, UnknownFormat};
+use crate::solution::Day;
+use crate::{Part, Result};
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// One separately timed step of solving a day.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parse"),
+            Self::Part(part) => write!(f, "part{}", part.number()),
+        }
+    }
+}
+
+/// Wall times of repeated runs of one stage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarises `samples`, or returns `None` if there are none.
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        let mut samples = samples.to_vec();
+        samples.sort();
+        let mid = samples.len() / 2;
+        let median = match samples.len() {
+            0 => return None,
+            n if n % 2 == 0 => (samples[mid - 1] + samples[mid]) / 2,
+            _ => samples[mid],
+        };
+
+        Some(Self {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        })
+    }
+}
+
+/// The timings of one stage of one day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub day: u8,
+    pub stage: Stage,
+    pub iterations: usize,
+    pub stats: Stats,
+}
+
+/// Benchmarks one day on `text`, parsing it once up front so a bad input is
+/// reported before any timing starts.
+pub fn run(day: &Day, text: &str, parts: &[Part], iterations: usize) -> Result<Vec<Report>> {
+    let stages = day.solver.bench(text, parts, iterations)?;
+    Ok(stages
+        .into_iter()
+        .filter_map(|(stage, samples)| {
+            Stats::new(&samples).map(|stats| Report {
+                day: day.number,
+                stage,
+                iterations,
+                stats,
+            })
+        })
+        .collect())
+}
+
+/// How benchmark results are written out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(text: &str) -> std::result::Result<Self, Self::Err> {
+        match text {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "markdown" | "md" => Ok(Self::Markdown),
+            other => Err(UnknownFormat {
+                found: other.to_owned(),
+                expected: "text, json or markdown",
+            }),
+        }
+    }
+}
+
+impl Format {
+    pub fn render(self, reports: &[Report]) -> String {
+        match self {
+            Self::Text => text(reports),
+            Self::Json => json(reports),
+            Self::Markdown => markdown(reports),
+        }
+    }
+}
+
+fn text(reports: &[Report]) -> String {
+    let mut out = String::new();
+    for r in reports {
+        // Writing to a String cannot fail.
+        let _ = writeln!(
+            out,
+            "day{} {}: min {:.2?}, median {:.2?}, max {:.2?} ({} runs)",
+            r.day, r.stage, r.stats.min, r.stats.median, r.stats.max, r.iterations
+        );
+    }
+    out
+}
+
+/// Times are in nanoseconds so runs can be compared exactly.
+fn json(reports: &[Report]) -> String {
+    json_array(reports.iter().map(|r| {
+        json_object(&[
+            ("day", r.day.to_string()),
+            ("stage", json_string(&r.stage.to_string())),
+            ("iterations", r.iterations.to_string()),
+            ("min_ns", r.stats.min.as_nanos().to_string()),
+            ("median_ns", r.stats.median.as_nanos().to_string()),
+            ("max_ns", r.stats.max.as_nanos().to_string()),
+        ])
+    }))
+}
+
+fn markdown(reports: &[Report]) -> String {
+    let mut out = String::from("| Day | Stage | Runs | Min | Median | Max |\n");
+    out.push_str("|----:|-------|-----:|----:|-------:|----:|\n");
+    for r in reports {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {:.2?} | {:.2?} | {:.2?} |",
+            r.day, r.stage, r.iterations, r.stats.min, r.stats.median, r.stats.max
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn reports() -> Vec<Report> {
+        vec![
+            Report {
+                day: 1,
+                stage: Stage::Parse,
+                iterations: 3,
+                stats: Stats::new(&[ms(2), ms(1), ms(3)]).unwrap(),
+            },
+            Report {
+                day: 1,
+                stage: Stage::Part(Part::Two),
+                iterations: 2,
+                stats: Stats::new(&[ms(4), ms(2)]).unwrap(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(Stats::new(&[]), None);
+        assert_eq!(
+            Stats::new(&[ms(5), ms(1), ms(3)]),
+            Some(Stats {
+                min: ms(1),
+                median: ms(3),
+                max: ms(5)
+            })
+        );
+        assert_eq!(
+            Stats::new(&[ms(4), ms(1), ms(2), ms(9)]).unwrap().median,
+            ms(3)
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            Format::Text.render(&reports()),
+            "day1 parse: min 1.00ms, median 2.00ms, max 3.00ms (3 runs)\n\
+             day1 part2: min 2.00ms, median 3.00ms, max 4.00ms (2 runs)\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(Format::Json.render(&[]), "[]\n");
+        assert_eq!(
+            Format::Json.render(&reports()[..1]),
+            "[\n  {\"day\": 1, \"stage\": \"parse\", \"iterations\": 3, \
+             \"min_ns\": 1000000, \"median_ns\": 2000000, \"max_ns\": 3000000}\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        let table = Format::Markdown.render(&reports());
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[3], "| 1 | part2 | 2 | 2.00ms | 3.00ms | 4.00ms |");
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("md".parse::<Format>().unwrap(), Format::Markdown);
+        assert_eq!(
+            "csv".parse::<Format>().unwrap_err().to_string(),
+            "expected text, json or markdown, found \"csv\""
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! Working out which value each key stands for, when every key starts with a
+//! set of candidates and no two keys can share a value.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// The values each key could still stand for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidates<K, V> {
+    sets: BTreeMap<K, BTreeSet<V>>,
+}
+
+impl<K, V> Default for Candidates<K, V> {
+    fn default() -> Self {
+        Self {
+            sets: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Ord + Clone, V: Ord + Clone> Candidates<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Narrows `key` down to values that are also in `values`. The first time
+    /// a key is seen, its candidates are `values`.
+    pub fn restrict(&mut self, key: K, values: impl IntoIterator<Item = V>) {
+        let values: BTreeSet<V> = values.into_iter().collect();
+        match self.sets.get_mut(&key) {
+            Some(set) => set.retain(|v| values.contains(v)),
+            None => {
+                self.sets.insert(key, values);
+            }
+        }
+    }
+
+    /// Rules out `value` for `key`.
+    pub fn exclude(&mut self, key: &K, value: &V) {
+        if let Some(set) = self.sets.get_mut(key) {
+            set.remove(value);
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&BTreeSet<V>> {
+        self.sets.get(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &BTreeSet<V>)> {
+        self.sets.iter()
+    }
+
+    /// Whether any key could stand for `value`.
+    pub fn is_possible(&self, value: &V) -> bool {
+        self.sets.values().any(|set| set.contains(value))
+    }
+
+    /// Repeatedly assigns keys with a single candidate and rules that value
+    /// out for every other key, until every key is assigned.
+    pub fn solve(mut self) -> Result<BTreeMap<K, V>, Unsolvable<K, V>> {
+        let mut solution = BTreeMap::new();
+
+        while !self.sets.is_empty() {
+            if let Some((key, _)) = self.sets.iter().find(|(_, set)| set.is_empty()) {
+                return Err(Unsolvable::Contradiction(key.clone()));
+            }
+
+            let (key, value) = match self.sets.iter().find(|(_, set)| set.len() == 1) {
+                Some((key, set)) => (key.clone(), set.iter().next().unwrap().clone()),
+                None => return Err(Unsolvable::UnderConstrained(self)),
+            };
+            self.sets.remove(&key);
+            for set in self.sets.values_mut() {
+                set.remove(&value);
+            }
+            solution.insert(key, value);
+        }
+
+        Ok(solution)
+    }
+}
+
+/// Why `Candidates::solve` could not assign every key.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Unsolvable<K, V> {
+    /// Every candidate for the key was ruled out.
+    Contradiction(K),
+    /// Every key left has more than one candidate. Holds what was left.
+    UnderConstrained(Candidates<K, V>),
+}
+
+impl<K: fmt::Display, V: fmt::Display> fmt::Display for Unsolvable<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Contradiction(key) => write!(f, "no candidates left for {}", key),
+            Self::UnderConstrained(candidates) => {
+                write!(f, "more than one candidate left for")?;
+                for (i, (key, set)) in candidates.sets.iter().enumerate() {
+                    let values: Vec<String> = set.iter().map(V::to_string).collect();
+                    let separator = if i == 0 { "" } else { ";" };
+                    write!(f, "{} {} ({})", separator, key, values.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<K: fmt::Debug + fmt::Display, V: fmt::Debug + fmt::Display> std::error::Error
+    for Unsolvable<K, V>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let mut candidates = Candidates::new();
+        candidates.restrict("a", vec![1, 2, 3]);
+        candidates.restrict("b", vec![1, 2]);
+        candidates.restrict("c", vec![2, 4]);
+        candidates.restrict("c", vec![2, 3]);
+        assert_eq!(candidates.get(&"c").unwrap().len(), 1);
+        assert!(!candidates.is_possible(&4));
+
+        let solution = candidates.solve().unwrap();
+        assert_eq!(
+            solution.into_iter().collect::<Vec<_>>(),
+            [("a", 3), ("b", 1), ("c", 2)]
+        );
+    }
+
+    #[test]
+    fn test_contradiction() {
+        let mut candidates = Candidates::new();
+        candidates.restrict("a", vec![1]);
+        candidates.restrict("b", vec![1]);
+        let error = candidates.solve().unwrap_err();
+        assert_eq!(error, Unsolvable::Contradiction("b"));
+        assert_eq!(error.to_string(), "no candidates left for b");
+    }
+
+    #[test]
+    fn test_under_constrained() {
+        let mut candidates = Candidates::new();
+        candidates.restrict("a", vec![1, 2]);
+        candidates.restrict("b", vec![1, 2]);
+        candidates.restrict("c", vec![3]);
+        candidates.exclude(&"a", &3);
+        assert_eq!(
+            candidates.solve().unwrap_err().to_string(),
+            "more than one candidate left for a (1, 2); b (1, 2)"
+        );
+    }
+}
@@ -1,28 +1,26 @@
-use input;
-use std::io::Result;
-fn main() -> Result<()> {
-    let expense_report = input::load_file_by_lines("src/day1/input.txt")?;
-    let expense_report: Vec<u32> = expense_report
-        .into_iter()
-        .map(|a| a.parse::<u32>().expect("parse failed"))
-        .collect();
+use crate::{Result, Solution};
 
-    println!(
-        "part1: {}",
-        part1(&expense_report).expect("didn't get an answer for part1")
-    );
+pub struct Day1;
 
-    println!(
-        "part2: {}",
-        part2(&expense_report).expect("didn't get an answer for part2")
-    );
+impl Solution for Day1 {
+    type Input<'a> = Vec<u32>;
 
-    Ok(())
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        crate::parse_lines(text)
+    }
+
+    fn part1(&self, expense_report: &Self::Input<'_>) -> Option<String> {
+        part1(expense_report).map(|a| a.to_string())
+    }
+
+    fn part2(&self, expense_report: &Self::Input<'_>) -> Option<String> {
+        part2(expense_report).map(|a| a.to_string())
+    }
 }
 
 /// Before you leave, the Elves in accounting just need you to fix your expense report (your puzzle input); apparently, something isn't quite adding up.
 /// Specifically, they need you to find the two entries that sum to 2020 and then multiply those two numbers together.
-fn part1(expense_report: &Vec<u32>) -> Option<u32> {
+pub fn part1(expense_report: &[u32]) -> Option<u32> {
     for i in 0..expense_report.len() - 1 {
         for j in i..expense_report.len() {
             let a = expense_report[i];
@@ -36,7 +34,7 @@ fn part1(expense_report: &Vec<u32>) -> Option<u32> {
 }
 
 /// In your expense report, what is the product of the three entries that sum to 2020?
-fn part2(expense_report: &Vec<u32>) -> Option<u32> {
+pub fn part2(expense_report: &[u32]) -> Option<u32> {
     for i in 0..expense_report.len() - 2 {
         for j in i..expense_report.len() - 1 {
             for k in j..expense_report.len() {
@@ -1,18 +1,26 @@
-use input;
-use std::io::Result;
+use crate::{Error, Result, Solution};
 
-fn main() -> Result<()> {
-    let content = input::load_file("src/day10/input.txt")?;
+use std::str::FromStr;
 
-    println!("part1: {}", part1(&content));
-    let bag = Bag::from(&content);
-    println!("part2: {}", number_of_arrangements_recursion(&bag.0));
+pub struct Day10;
 
-    Ok(())
+impl Solution for Day10 {
+    type Input<'a> = Bag;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        text.parse()
+    }
+
+    fn part1(&self, bag: &Self::Input<'_>) -> Option<String> {
+        Some(part1(bag).to_string())
+    }
+
+    fn part2(&self, bag: &Self::Input<'_>) -> Option<String> {
+        Some(number_of_arrangements(&bag.0).to_string())
+    }
 }
 
-pub fn part1(text: &str) -> usize {
-    let bag = Bag::from(text);
+pub fn part1(bag: &Bag) -> usize {
     let (ones, threes) = bag.differences();
     ones * threes
 }
@@ -21,18 +29,19 @@ pub type Adapter = usize;
 
 pub struct Bag(Vec<Adapter>);
 
-impl Bag {
-    pub fn from(text: &str) -> Self {
-        let mut adapters: Vec<Adapter> = text
-            .lines()
-            .map(|line| line.parse().expect("parse failed"))
-            .collect();
+impl FromStr for Bag {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let mut adapters: Vec<Adapter> = crate::parse_lines(text)?;
         let mut with_zero = vec![0];
         with_zero.append(&mut adapters);
         with_zero.sort();
-        Bag(with_zero)
+        Ok(Bag(with_zero))
     }
+}
 
+impl Bag {
     pub fn differences(&self) -> (usize, usize) {
         let mut ones = 0;
         let mut threes = 1;
@@ -91,30 +100,24 @@ pub fn number_of_arrangements_recursion(adapters: &[Adapter]) -> usize {
     }
 }
 
+/// Counts the same arrangements as `number_of_arrangements_recursion`, in
+/// linear time.
 pub fn number_of_arrangements(adapters: &[Adapter]) -> usize {
-    let mut num = 1;
-    let mut i = adapters.len() - 1;
-
-    // going backwards through the list.
-    while i >= 3 {
-        let j = i - 2;
-        let k = i - 3;
-
-        if (adapters[i] - adapters[k]) <= 3 {
-            num *= 4;
-            i -= 2;
-        } else if (adapters[i] - adapters[j]) <= 3 {
-            num *= 2;
-            i -= 1;
-        } else {
-            i -= 1;
-        }
+    // ways[i] is the number of arrangements that end at adapters[i].
+    let mut ways = vec![0; adapters.len()];
+    ways[0] = 1;
+
+    for i in 1..adapters.len() {
+        ways[i] = (i.saturating_sub(3)..i)
+            .filter(|&j| adapters[i] - adapters[j] <= 3)
+            .map(|j| ways[j])
+            .sum();
     }
 
-    num
+    *ways.last().expect("bag has no adapters")
 }
 
-fn combos(n: usize) -> usize {
+pub fn combos(n: usize) -> usize {
     match n {
         0 => 0,
         1 => 1,
@@ -140,7 +143,7 @@ mod tests {
 6
 12
 4";
-        let bag = Bag::from(&text);
+        let bag: Bag = text.parse().unwrap();
 
         assert_eq!(bag.differences(), (7, 5));
     }
@@ -178,7 +181,7 @@ mod tests {
 34
 10
 3";
-        let bag = Bag::from(&text);
+        let bag: Bag = text.parse().unwrap();
 
         assert_eq!(bag.differences(), (22, 10));
     }
@@ -196,7 +199,7 @@ mod tests {
 6
 12
 4";
-        let bag = Bag::from(&text);
+        let bag: Bag = text.parse().unwrap();
 
         assert_eq!(number_of_arrangements(&bag.0), 8);
     }
@@ -234,8 +237,9 @@ mod tests {
 34
 10
 3";
-        let bag = Bag::from(&text);
+        let bag: Bag = text.parse().unwrap();
 
         assert_eq!(number_of_arrangements(&bag.0), 19208);
+        assert_eq!(number_of_arrangements_recursion(&bag.0), 19208);
     }
 }
@@ -0,0 +1,494 @@
+use crate::grid::{Grid, Position as Seat, DIRECTIONS};
+use crate::{Error, Result, Solution};
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Layout;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        text.parse()
+    }
+
+    fn part1(&self, layout: &Self::Input<'_>) -> Option<String> {
+        let mut layout = layout.clone();
+        layout.simulate(Rules::ADJACENT).map(|n| n.to_string())
+    }
+
+    fn part2(&self, layout: &Self::Input<'_>) -> Option<String> {
+        let mut layout = layout.clone();
+        layout.simulate(Rules::VISIBLE).map(|n| n.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Position {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl TryFrom<char> for Position {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            '.' => Ok(Self::Floor),
+            'L' => Ok(Self::Empty),
+            '#' => Ok(Self::Occupied),
+            c => Err(Error::unexpected(1, c, "'.', 'L' or '#'")),
+        }
+    }
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Floor => '.',
+                Self::Empty => 'L',
+                Self::Occupied => '#',
+            }
+        )
+    }
+}
+
+/// Which seats count as a seat's neighbours.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Neighbours {
+    /// The seats in the eight cells touching it.
+    Adjacent,
+    /// The first seat seen in each of the eight directions, looking past
+    /// floor.
+    Visible,
+}
+
+impl Neighbours {
+    /// The neighbouring seats of `seat`. Floor is never a neighbour.
+    pub fn of(self, layout: &Grid<Position>, seat: Seat) -> Vec<Seat> {
+        match self {
+            Self::Adjacent => layout
+                .neighbours8(seat)
+                .filter(|(_, p)| **p != Position::Floor)
+                .map(|(seat, _)| seat)
+                .collect(),
+            Self::Visible => DIRECTIONS
+                .iter()
+                .filter_map(|&d| {
+                    layout
+                        .ray(seat, d)
+                        .find(|(_, p)| **p != Position::Floor)
+                        .map(|(seat, _)| seat)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// How seats change each round.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rules {
+    pub neighbours: Neighbours,
+    /// An occupied seat is left when at least this many neighbours are
+    /// occupied.
+    pub tolerance: usize,
+}
+
+impl Rules {
+    /// Part 1: four or more occupied adjacent seats.
+    pub const ADJACENT: Self = Self {
+        neighbours: Neighbours::Adjacent,
+        tolerance: 4,
+    };
+
+    /// Part 2: five or more occupied visible seats.
+    pub const VISIBLE: Self = Self {
+        neighbours: Neighbours::Visible,
+        tolerance: 5,
+    };
+
+    /// implement the seat change rules applied simultaneously
+    pub fn change(&self, p: Position, neighbors: usize) -> Option<Position> {
+        match p {
+            Position::Empty if neighbors == 0 => Some(Position::Occupied),
+            Position::Occupied if neighbors >= self.tolerance => Some(Position::Empty),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Layout {
+    layout: Grid<Position>,
+}
+
+impl std::fmt::Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.layout)
+    }
+}
+
+impl FromStr for Layout {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Ok(Self {
+            layout: text.parse()?,
+        })
+    }
+}
+
+impl Layout {
+    /// Runs rounds until no seat changes and counts the occupied seats.
+    ///
+    /// With a low tolerance seats can keep changing forever, e.g. a lone seat
+    /// with a tolerance of 0 empties as soon as it fills. Returns `None` once
+    /// the layout repeats without settling.
+    pub fn simulate(&mut self, rules: Rules) -> Option<usize> {
+        // the neighbours never change, so only look for them once.
+        let neighbours = self.neighbours(rules.neighbours);
+        let mut seen = HashSet::new();
+
+        // run rounds until the number of seat changes is zero.
+        while self.do_round(&rules, &neighbours) != 0 {
+            if !seen.insert(self.layout.cells().to_vec()) {
+                return None;
+            }
+        }
+
+        // count the number of occupied seats when the layout stabilizes.
+        let occupied = self
+            .layout
+            .cells()
+            .iter()
+            .filter(|p| matches!(p, Position::Occupied))
+            .count();
+        Some(occupied)
+    }
+
+    /// Each seat's neighbours under `policy`.
+    pub fn neighbours(&self, policy: Neighbours) -> Grid<Vec<Seat>> {
+        let cells = self
+            .layout
+            .iter()
+            .map(|(seat, _)| policy.of(&self.layout, seat))
+            .collect();
+        Grid::new(self.layout.rows(), self.layout.cols(), cells)
+    }
+
+    fn do_round(&mut self, rules: &Rules, neighbours: &Grid<Vec<Seat>>) -> usize {
+        let mut num_changes = 0;
+
+        // clone the layout to record the seat changes.
+        // if we mutated the layout, the "changes" wouldn't be simultaneous.
+        let mut new_layout = self.layout.clone();
+
+        self.layout.iter().for_each(|(seat, p)| {
+            let neighbors = self.num_neighbors_occupied(&neighbours[seat]);
+            if let Some(p) = rules.change(*p, neighbors) {
+                new_layout[seat] = p;
+                num_changes += 1;
+            }
+        });
+
+        self.layout = new_layout;
+
+        num_changes
+    }
+
+    fn num_neighbors_occupied(&self, neighbours: &[Seat]) -> usize {
+        neighbours
+            .iter()
+            .filter(|&&seat| self.layout[seat] == Position::Occupied)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lcg;
+
+    static EXAMPLE: &str = "L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\nL.LLLLL.LL\n..L.L.....\nLLLLLLLLLL\nL.LLLLLL.L\nL.LLLLL.LL";
+
+    fn occupied(layout: &Layout, seat: Seat, policy: Neighbours) -> usize {
+        layout.num_neighbors_occupied(&policy.of(&layout.layout, seat))
+    }
+
+    fn rounds(layout: &mut Layout, rules: Rules, rounds: usize) {
+        let neighbours = layout.neighbours(rules.neighbours);
+        for _ in 0..rounds {
+            layout.do_round(&rules, &neighbours);
+        }
+    }
+
+    #[test]
+    fn test_num_neighbors_occupied() {
+        let text = "#.LL.L#.##\n#LLLLLL.L#\nL.L.L..L..\n#LLL.LL.L#\n#.LL.LL.LL\n#.LLLL#.##\n..L.L.....\n#LLLLLLLL#\n#.LLLLLL.L\n#.#LLLL.##";
+
+        let layout: Layout = text.parse().unwrap();
+
+        assert_eq!(occupied(&layout, (0, 0), Neighbours::Adjacent), 1);
+        assert_eq!(occupied(&layout, (0, 6), Neighbours::Adjacent), 0);
+        assert_eq!(occupied(&layout, (0, 9), Neighbours::Adjacent), 2);
+    }
+
+    #[test]
+    fn test_visible_neighbors_occupied() {
+        let text = ".......#.\n...#.....\n.#.......\n.........\n..#L....#\n....#....\n.........\n#........\n...#.....";
+        let layout: Layout = text.parse().unwrap();
+        assert_eq!(occupied(&layout, (4, 3), Neighbours::Visible), 8);
+        assert_eq!(occupied(&layout, (4, 3), Neighbours::Adjacent), 2);
+
+        // the empty seat blocks the view of the occupied ones behind it.
+        let layout: Layout = ".............\n.L.L.#.#.#.#.\n............."
+            .parse()
+            .unwrap();
+        assert_eq!(occupied(&layout, (1, 1), Neighbours::Visible), 0);
+        assert_eq!(Neighbours::Visible.of(&layout.layout, (1, 1)), [(1, 3)]);
+
+        let text = ".##.##.\n#.#.#.#\n##...##\n...L...\n##...##\n#.#.#.#\n.##.##.";
+        let layout: Layout = text.parse().unwrap();
+        assert_eq!(occupied(&layout, (3, 3), Neighbours::Visible), 0);
+    }
+
+    #[test]
+    fn test_rules() {
+        // If a seat is empty (L) and there are no occupied seats adjacent to it, the seat becomes occupied.
+        let rules = Rules::ADJACENT;
+        assert_eq!(rules.change(Position::Empty, 0), Some(Position::Occupied));
+        assert_eq!(rules.change(Position::Empty, 1), None);
+
+        // If a seat is occupied (#) and four or more seats adjacent to it are also occupied, the seat becomes empty.
+        assert_eq!(rules.change(Position::Occupied, 0), None);
+        assert_eq!(rules.change(Position::Occupied, 3), None);
+        assert_eq!(rules.change(Position::Occupied, 4), Some(Position::Empty));
+        assert_eq!(rules.change(Position::Occupied, 5), Some(Position::Empty));
+
+        // Otherwise, the seat's state does not change.
+        assert_eq!(rules.change(Position::Floor, 0), None);
+        assert_eq!(rules.change(Position::Floor, 5), None);
+
+        // Part 2 tolerates four occupied seats.
+        assert_eq!(Rules::VISIBLE.change(Position::Occupied, 4), None);
+        assert_eq!(
+            Rules::VISIBLE.change(Position::Occupied, 5),
+            Some(Position::Empty)
+        );
+    }
+
+    #[test]
+    fn test_layout_display() {
+        let text = "L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\nL.LLLLL.LL\n..L.L.....\nLLLLLLLLLL\nL.LLLLLL.L\nL.LLLLL.LL";
+
+        let layout: Layout = text.parse().unwrap();
+
+        assert_eq!(text, format!("{}", layout));
+    }
+
+    #[test]
+    fn test_part1() {
+        let text = "L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\nL.LLLLL.LL\n..L.L.....\nLLLLLLLLLL\nL.LLLLLL.L\nL.LLLLL.LL";
+
+        let mut layout: Layout = text.parse().unwrap();
+
+        assert_eq!(layout.simulate(Rules::ADJACENT), Some(37));
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut layout: Layout = EXAMPLE.parse().unwrap();
+        let round2 = "#.LL.LL.L#\n#LLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\nL.LLLLL.LL\n..L.L.....\nLLLLLLLLL#\n#.LLLLLL.L\n#.LLLLL.L#";
+        rounds(&mut layout, Rules::VISIBLE, 2);
+        assert_eq!(round2, layout.to_string());
+
+        let layout: Layout = EXAMPLE.parse().unwrap();
+        assert_eq!(Day11.part2(&layout), Some("26".to_owned()));
+
+        // a variant that tolerates five occupied visible seats.
+        let mut layout: Layout = EXAMPLE.parse().unwrap();
+        let lenient = Rules {
+            neighbours: Neighbours::Visible,
+            tolerance: 6,
+        };
+        assert_eq!(layout.simulate(lenient), Some(40));
+    }
+
+    #[test]
+    fn test_never_settles() {
+        let restless = |tolerance| Rules {
+            neighbours: Neighbours::Adjacent,
+            tolerance,
+        };
+
+        let mut layout: Layout = "L".parse().unwrap();
+        assert_eq!(layout.simulate(restless(0)), None);
+        let mut layout: Layout = "L".parse().unwrap();
+        assert_eq!(layout.simulate(restless(1)), Some(1));
+
+        let mut layout: Layout = EXAMPLE.parse().unwrap();
+        assert_eq!(layout.simulate(restless(2)), None);
+    }
+
+    #[test]
+    fn test_round1() {
+        let text = "L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\nL.LLLLL.LL\n..L.L.....\nLLLLLLLLLL\nL.LLLLLL.L\nL.LLLLL.LL";
+        let round1 = "#.##.##.##\n#######.##\n#.#.#..#..\n####.##.##\n#.##.##.##\n#.#####.##\n..#.#.....\n##########\n#.######.#\n#.#####.##";
+
+        let mut layout: Layout = text.parse().unwrap();
+        rounds(&mut layout, Rules::ADJACENT, 1);
+
+        assert_eq!(round1, format!("{}", layout));
+    }
+
+    #[test]
+    fn test_round2() {
+        let text = "L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\nL.LLLLL.LL\n..L.L.....\nLLLLLLLLLL\nL.LLLLLL.L\nL.LLLLL.LL";
+        let round2 = "#.LL.L#.##\n#LLLLLL.L#\nL.L.L..L..\n#LLL.LL.L#\n#.LL.LL.LL\n#.LLLL#.##\n..L.L.....\n#LLLLLLLL#\n#.LLLLLL.L\n#.#LLLL.##";
+
+        let mut layout: Layout = text.parse().unwrap();
+        rounds(&mut layout, Rules::ADJACENT, 2);
+
+        assert_eq!(round2, format!("{}", layout));
+    }
+
+    #[test]
+    fn test_round3() {
+        let text = "L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\nL.LLLLL.LL\n..L.L.....\nLLLLLLLLLL\nL.LLLLLL.L\nL.LLLLL.LL";
+        let round3 = "#.##.L#.##\n#L###LL.L#\nL.#.#..#..\n#L##.##.L#\n#.##.LL.LL\n#.###L#.##\n..#.#.....\n#L######L#\n#.LL###L.L\n#.#L###.##";
+
+        let mut layout: Layout = text.parse().unwrap();
+        rounds(&mut layout, Rules::ADJACENT, 3);
+
+        assert_eq!(round3, format!("{}", layout));
+    }
+
+    #[test]
+    fn test_layout_from_str_errors() {
+        assert_eq!(
+            "L.L\nL?L".parse::<Layout>().err().unwrap().to_string(),
+            "line 2, column 2: expected '.', 'L' or '#', found \"?\""
+        );
+        assert!("L.L\nL.".parse::<Layout>().is_err());
+        assert!("".parse::<Layout>().is_err());
+    }
+
+    #[test]
+    fn test_rectangular() {
+        // Wider than it is tall, so mixing up rows and columns goes out of
+        // bounds or picks the wrong neighbours.
+        let text = "L.L.L.L\nLLLLLLL\nL.LL..L";
+        let mut layout: Layout = text.parse().unwrap();
+        assert_eq!(layout.simulate(Rules::ADJACENT), Some(10));
+        let mut layout: Layout = text.parse().unwrap();
+        assert_eq!(layout.simulate(Rules::VISIBLE), Some(8));
+
+        let mut layout: Layout = "LLLLL\nLLLLL".parse().unwrap();
+        assert_eq!(layout.simulate(Rules::ADJACENT), Some(6));
+        assert_eq!(layout.to_string(), "#L#L#\n#L#L#");
+
+        let mut layout: Layout = transpose(text).parse().unwrap();
+        assert_eq!(layout.simulate(Rules::ADJACENT), Some(10));
+    }
+
+    fn transpose(text: &str) -> String {
+        let rows: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+        (0..rows[0].len())
+            .map(|c| rows.iter().map(|row| row[c]).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Simulates the seats straight from the puzzle description, recomputing
+    /// every seat's neighbours with plain index arithmetic each round.
+    fn reference(text: &str, rules: Rules) -> (usize, String) {
+        let mut seats: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+        let (rows, cols) = (seats.len() as isize, seats[0].len() as isize);
+
+        loop {
+            let mut next = seats.clone();
+            for r in 0..rows {
+                for c in 0..cols {
+                    let mut occupied = 0;
+                    for &(dr, dc) in &DIRECTIONS {
+                        let (mut y, mut x) = (r + dr, c + dc);
+                        while y >= 0 && y < rows && x >= 0 && x < cols {
+                            let seat = seats[y as usize][x as usize];
+                            if seat == '#' {
+                                occupied += 1;
+                            }
+                            if seat != '.' || rules.neighbours == Neighbours::Adjacent {
+                                break;
+                            }
+                            y += dr;
+                            x += dc;
+                        }
+                    }
+
+                    let cell = &mut next[r as usize][c as usize];
+                    match *cell {
+                        'L' if occupied == 0 => *cell = '#',
+                        '#' if occupied >= rules.tolerance => *cell = 'L',
+                        _ => {}
+                    }
+                }
+            }
+
+            if next == seats {
+                let count = seats.iter().flatten().filter(|&&c| c == '#').count();
+                let text: Vec<String> = seats.iter().map(|row| row.iter().collect()).collect();
+                return (count, text.join("\n"));
+            }
+            seats = next;
+        }
+    }
+
+    /// Rectangular layouts of random shapes, mostly seats with some floor.
+    fn random_layouts(count: usize) -> Vec<String> {
+        let mut lcg = Lcg::new(11);
+        let mut random = move |n: usize| lcg.below(n);
+
+        (0..count)
+            .map(|_| {
+                let (rows, cols) = (1 + random(15), 1 + random(15));
+                (0..rows)
+                    .map(|_| {
+                        (0..cols)
+                            .map(|_| if random(4) == 0 { '.' } else { 'L' })
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        let layouts = random_layouts(200);
+        assert!(layouts.iter().any(|l| {
+            let rows = l.lines().count();
+            rows != l.lines().next().unwrap().len() && rows > 1
+        }));
+
+        for text in &layouts {
+            for &rules in &[Rules::ADJACENT, Rules::VISIBLE] {
+                let mut layout: Layout = text.parse().unwrap();
+                let count = layout.simulate(rules).unwrap();
+                assert_eq!(
+                    (count, layout.to_string()),
+                    reference(text, rules),
+                    "{:?} on\n{}",
+                    rules,
+                    text
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use crate::error::parse_number;
+use crate::{Error, Result, Solution};
+
+use std::str::FromStr;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = Ship;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        text.parse()
+    }
+
+    fn part1(&self, ship: &Self::Input<'_>) -> Option<String> {
+        let mut ship = ship.clone();
+        ship.execute_instructions();
+        Some(ship.manhatten_distance().to_string())
+    }
+
+    fn part2(&self, ship: &Self::Input<'_>) -> Option<String> {
+        let mut ship = ship.clone().with_navigation(Navigation::Waypoint);
+        ship.execute_instructions();
+        Some(ship.manhatten_distance().to_string())
+    }
+}
+
+pub type Value = isize;
+
+pub type Degrees = isize;
+
+/// Action N means to move north by the given value.
+/// Action S means to move south by the given value.
+/// Action E means to move east by the given value.
+/// Action W means to move west by the given value.
+/// Action L means to turn left the given number of degrees.
+/// Action R means to turn right the given number of degrees.
+/// Action F means to move forward by the given value in the direction the ship is currently facing.
+///
+/// Turns are always whole right angles; anything else fails to parse.
+#[derive(Clone, Copy)]
+pub enum Action {
+    N(Value),
+    S(Value),
+    E(Value),
+    W(Value),
+    L(Degrees),
+    R(Degrees),
+    F(Value),
+}
+
+impl FromStr for Action {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        let action = chars
+            .next()
+            .ok_or_else(|| Error::unexpected(1, "end of line", "an action"))?;
+        let v = parse_number::<Value>(chars.as_str(), 2)?;
+
+        if matches!(action, 'L' | 'R') && v % 90 != 0 {
+            let message = format!("expected a multiple of 90 degrees, found {}", v);
+            return Err(Error::parse(2, message));
+        }
+
+        match action {
+            'N' => Ok(Self::N(v)),
+            'S' => Ok(Self::S(v)),
+            'E' => Ok(Self::E(v)),
+            'W' => Ok(Self::W(v)),
+            'L' => Ok(Self::L(v)),
+            'R' => Ok(Self::R(v)),
+            'F' => Ok(Self::F(v)),
+            c => Err(Error::unexpected(1, c, "one of N, S, E, W, L, R, F")),
+        }
+    }
+}
+
+type NavigationInstructions = Vec<Action>;
+
+/// How the ship interprets its instructions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Navigation {
+    /// N, S, E and W move the ship, L and R turn it and F moves it the way
+    /// it is facing.
+    Heading,
+    /// N, S, E and W move the waypoint, L and R rotate it around the ship and
+    /// F moves the ship to the waypoint the given number of times.
+    Waypoint,
+}
+
+/// Where the waypoint starts, relative to the ship: 10 east and 1 north.
+const WAYPOINT: (isize, isize) = (10, 1);
+
+#[derive(Clone)]
+pub struct Ship {
+    instructions: NavigationInstructions,
+    navigation: Navigation,
+    direction: Degrees,
+    position: (isize, isize),
+    /// Relative to the ship, and only used with `Navigation::Waypoint`.
+    waypoint: (isize, isize),
+}
+
+impl FromStr for Ship {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self {
+            instructions: crate::parse_lines(s)?,
+            ..Self::no_instructions()
+        })
+    }
+}
+
+impl Ship {
+    pub fn no_instructions() -> Self {
+        Self {
+            instructions: Vec::new(),
+            navigation: Navigation::Heading,
+            direction: 0,
+            position: (0, 0),
+            waypoint: WAYPOINT,
+        }
+    }
+
+    pub fn with_navigation(mut self, navigation: Navigation) -> Self {
+        self.navigation = navigation;
+        self
+    }
+
+    pub fn execute_instructions(&mut self) {
+        (0..self.instructions.len())
+            .for_each(|i| self.do_action(*self.instructions.get(i).unwrap()));
+    }
+
+    fn do_action(&mut self, a: Action) {
+        match self.navigation {
+            Navigation::Heading => self.do_heading_action(a),
+            Navigation::Waypoint => self.do_waypoint_action(a),
+        }
+    }
+
+    fn do_heading_action(&mut self, a: Action) {
+        match a {
+            Action::N(v) => self.position.1 += v,
+            Action::S(v) => self.position.1 -= v,
+            Action::E(v) => self.position.0 += v,
+            Action::W(v) => self.position.0 -= v,
+            Action::L(d) => self.direction = (self.direction + d) % 360,
+            Action::R(d) => self.direction = (self.direction - d + 360) % 360,
+            Action::F(v) => {
+                let (x, y) = rotate((v, 0), self.direction);
+                self.position.0 += x;
+                self.position.1 += y;
+            }
+        }
+    }
+
+    fn do_waypoint_action(&mut self, a: Action) {
+        match a {
+            Action::N(v) => self.waypoint.1 += v,
+            Action::S(v) => self.waypoint.1 -= v,
+            Action::E(v) => self.waypoint.0 += v,
+            Action::W(v) => self.waypoint.0 -= v,
+            Action::L(d) => self.waypoint = rotate(self.waypoint, d),
+            Action::R(d) => self.waypoint = rotate(self.waypoint, -d),
+            Action::F(v) => {
+                self.position.0 += self.waypoint.0 * v;
+                self.position.1 += self.waypoint.1 * v;
+            }
+        }
+    }
+
+    pub fn manhatten_distance(&self) -> usize {
+        (self.position.0.abs() + self.position.1.abs()) as usize
+    }
+}
+
+/// Rotates an east/north offset anticlockwise by a multiple of 90 degrees.
+fn rotate((x, y): (isize, isize), degrees: Degrees) -> (isize, isize) {
+    match degrees.rem_euclid(360) {
+        0 => (x, y),
+        90 => (-y, x),
+        180 => (-x, -y),
+        270 => (y, -x),
+        d => unreachable!("turns are parsed as right angles, found {}", d),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_do_action() {
+        let mut ship = Ship::no_instructions();
+
+        // F10
+        ship.do_action(Action::F(10));
+        assert_eq!(ship.position, (10, 0));
+        assert_eq!(ship.direction, 0);
+
+        // N3
+        ship.do_action(Action::N(3));
+        assert_eq!(ship.position, (10, 3));
+        assert_eq!(ship.direction, 0);
+
+        // F7
+        ship.do_action(Action::F(7));
+        assert_eq!(ship.position, (17, 3));
+        assert_eq!(ship.direction, 0);
+
+        // R90
+        ship.do_action(Action::R(90));
+        assert_eq!(ship.position, (17, 3));
+        assert_eq!(ship.direction, 270);
+
+        // F11
+        ship.do_action(Action::F(11));
+        assert_eq!(ship.position, (17, -8));
+        assert_eq!(ship.direction, 270);
+    }
+
+    #[test]
+    fn test_part1() {
+        let s = "F10\nN3\nF7\nR90\nF11";
+        let mut ship: Ship = s.parse().unwrap();
+        ship.execute_instructions();
+        assert_eq!(ship.manhatten_distance(), 25);
+    }
+
+    #[test]
+    fn test_waypoint_action() {
+        let mut ship = Ship::no_instructions().with_navigation(Navigation::Waypoint);
+
+        // F10
+        ship.do_action(Action::F(10));
+        assert_eq!(ship.position, (100, 10));
+        assert_eq!(ship.waypoint, (10, 1));
+
+        // N3
+        ship.do_action(Action::N(3));
+        assert_eq!(ship.position, (100, 10));
+        assert_eq!(ship.waypoint, (10, 4));
+
+        // F7
+        ship.do_action(Action::F(7));
+        assert_eq!(ship.position, (170, 38));
+        assert_eq!(ship.waypoint, (10, 4));
+
+        // R90
+        ship.do_action(Action::R(90));
+        assert_eq!(ship.position, (170, 38));
+        assert_eq!(ship.waypoint, (4, -10));
+
+        // F11
+        ship.do_action(Action::F(11));
+        assert_eq!(ship.position, (214, -72));
+        assert_eq!(ship.waypoint, (4, -10));
+
+        ship.do_action(Action::L(270));
+        assert_eq!(ship.waypoint, (-10, -4));
+        ship.do_action(Action::R(-180));
+        assert_eq!(ship.waypoint, (10, 4));
+    }
+
+    #[test]
+    fn test_part2() {
+        let ship: Ship = "F10\nN3\nF7\nR90\nF11".parse().unwrap();
+        assert_eq!(Day12.part2(&ship), Some("286".to_owned()));
+        assert_eq!(Day12.part1(&ship), Some("25".to_owned()));
+    }
+
+    #[test]
+    fn test_turns() {
+        let mut ship: Ship = "L180\nF3\nR450\nF2\nL-90\nF1".parse().unwrap();
+        ship.execute_instructions();
+        assert_eq!(ship.position, (-2, 2));
+        assert_eq!(
+            "F10\nR45".parse::<Ship>().err().unwrap().to_string(),
+            "line 2, column 2: expected a multiple of 90 degrees, found 45"
+        );
+    }
+
+    #[test]
+    fn test_action_from_str_errors() {
+        assert_eq!(
+            "F10\nX3".parse::<Ship>().err().unwrap().to_string(),
+            "line 2, column 1: expected one of N, S, E, W, L, R, F, found \"X\""
+        );
+        assert_eq!(
+            "F10\nN".parse::<Ship>().err().unwrap().to_string(),
+            "line 2, column 2: cannot parse integer from empty string in \"\""
+        );
+        assert!("".parse::<Action>().is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::error::parse_number;
+use crate::modular::{self, Congruence};
+use crate::{Error, Result, Solution};
+
+use std::str::FromStr;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = Notes;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        text.parse()
+    }
+
+    fn part1(&self, notes: &Self::Input<'_>) -> Option<String> {
+        let (bus, wait) = notes.earliest_bus();
+        Some((bus * wait).to_string())
+    }
+
+    fn part2(&self, notes: &Self::Input<'_>) -> Option<String> {
+        notes.earliest_staggered_departure().map(|t| t.to_string())
+    }
+}
+
+pub type BusId = u128;
+
+/// The earliest timestamp we could leave at, and the bus schedule, where `x`
+/// marks a bus that is out of service.
+#[derive(Debug, PartialEq)]
+pub struct Notes {
+    earliest: u128,
+    buses: Vec<Option<BusId>>,
+}
+
+impl FromStr for Notes {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let mut lines = text.lines();
+        let earliest = parse_number(lines.next().unwrap_or_default().trim(), 1)?;
+        let schedule = lines
+            .next()
+            .ok_or_else(|| Error::parse(1, "missing bus schedule").offset(1, 0))?;
+
+        let mut column = 1;
+        let mut buses = Vec::new();
+        for id in schedule.trim_end().split(',') {
+            let bus = match id {
+                "x" => None,
+                id => match parse_number(id, column).map_err(|e| e.offset(1, 0))? {
+                    0 => return Err(Error::parse(column, "bus IDs start at 1").offset(1, 0)),
+                    id => Some(id),
+                },
+            };
+            buses.push(bus);
+            column += id.chars().count() + 1;
+        }
+        if buses.iter().all(Option::is_none) {
+            return Err(Error::parse(1, "no buses in service").offset(1, 0));
+        }
+
+        Ok(Self { earliest, buses })
+    }
+}
+
+impl Notes {
+    pub fn in_service(&self) -> impl Iterator<Item = (usize, BusId)> + '_ {
+        self.buses
+            .iter()
+            .enumerate()
+            .filter_map(|(offset, bus)| bus.map(|id| (offset, id)))
+    }
+
+    /// The first bus to leave at or after the earliest timestamp, and how long
+    /// we would wait for it.
+    pub fn earliest_bus(&self) -> (BusId, u128) {
+        self.in_service()
+            .map(|(_, id)| (id, (id - self.earliest % id) % id))
+            .min_by_key(|&(_, wait)| wait)
+            .expect("parsing checks some bus is in service")
+    }
+
+    /// The earliest timestamp at which each bus leaves as many minutes after
+    /// it as its position in the schedule, or `None` if that never happens.
+    pub fn earliest_staggered_departure(&self) -> Option<u128> {
+        let congruences: Vec<Congruence> = self
+            .in_service()
+            .map(|(offset, id)| {
+                let offset = offset as u128 % id;
+                Congruence::new((id - offset) % id, id)
+            })
+            .collect();
+
+        modular::crt(&congruences).ok().map(|c| c.residue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "939\n7,13,x,x,59,x,31,19";
+
+    fn staggered(schedule: &str) -> Option<u128> {
+        format!("0\n{}", schedule)
+            .parse::<Notes>()
+            .unwrap()
+            .earliest_staggered_departure()
+    }
+
+    #[test]
+    fn test_parse() {
+        let notes: Notes = EXAMPLE.parse().unwrap();
+        assert_eq!(notes.earliest, 939);
+        assert_eq!(
+            notes.buses,
+            vec![
+                Some(7),
+                Some(13),
+                None,
+                None,
+                Some(59),
+                None,
+                Some(31),
+                Some(19)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "939\n7,y,13".parse::<Notes>().unwrap_err().to_string(),
+            "line 2, column 3: invalid digit found in string in \"y\""
+        );
+        assert_eq!(
+            "939\n7,0".parse::<Notes>().unwrap_err().to_string(),
+            "line 2, column 3: bus IDs start at 1"
+        );
+        assert!("939".parse::<Notes>().is_err());
+        assert!("939\nx,x".parse::<Notes>().is_err());
+        assert!("soon\n7".parse::<Notes>().is_err());
+    }
+
+    #[test]
+    fn test_part1() {
+        let notes: Notes = EXAMPLE.parse().unwrap();
+        assert_eq!(notes.earliest_bus(), (59, 5));
+        assert_eq!(Day13.part1(&notes), Some("295".to_owned()));
+    }
+
+    #[test]
+    fn test_part2() {
+        let notes: Notes = EXAMPLE.parse().unwrap();
+        assert_eq!(notes.earliest_staggered_departure(), Some(1068781));
+
+        assert_eq!(staggered("17,x,13,19"), Some(3417));
+        assert_eq!(staggered("67,7,59,61"), Some(754018));
+        assert_eq!(staggered("67,x,7,59,61"), Some(779210));
+        assert_eq!(staggered("67,7,x,59,61"), Some(1261476));
+        assert_eq!(staggered("1789,37,47,1889"), Some(1202161486));
+    }
+
+    #[test]
+    fn test_part2_shared_factors() {
+        // 4 and 6 share a factor: t ≡ 0 (mod 4) and t ≡ 5 (mod 6) has no
+        // solution, but t ≡ 0 (mod 4) and t ≡ 4 (mod 6) does.
+        assert_eq!(staggered("4,6"), None);
+        assert_eq!(staggered("4,x,6"), Some(4));
+    }
+}
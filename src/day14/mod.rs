@@ -0,0 +1,475 @@
+use crate::error::{column, parse_number};
+use crate::{Error, Result, Solution};
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = Program<'a>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        crate::parse_lines_with(text, Instruction::try_from)
+    }
+
+    fn part1(&self, program: &Self::Input<'_>) -> Option<String> {
+        let mut computer = ComputerSystem::new();
+        program.iter().for_each(|i| computer.execute(i));
+        Some(computer.sum_memory().to_string())
+    }
+
+    fn part2(&self, program: &Self::Input<'_>) -> Option<String> {
+        let mut computer = ComputerSystem::new().with_decoder(Decoder::V2);
+        program.iter().for_each(|i| computer.execute(i));
+        Some(computer.sum_memory().to_string())
+    }
+}
+
+pub type Program<'a> = Vec<Instruction<'a>>;
+pub type Address = u64;
+pub type Value = u64;
+
+/// Which version of the docking program decoder chip is fitted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Decoder {
+    /// The mask applies to values written.
+    V1,
+    /// The mask applies to addresses, and floating bits write to every
+    /// address they can stand for.
+    V2,
+}
+
+pub struct ComputerSystem<'a> {
+    decoder: Decoder,
+    bitmask: Option<&'a BitMask<'a>>,
+    memory: HashMap<Address, Value>,
+    /// Writes made by the version 2 decoder, none of which overlap, so a
+    /// mask with many floating bits is still a single entry.
+    floating: Vec<(FloatingAddress, Value)>,
+}
+
+impl<'a> Default for ComputerSystem<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> ComputerSystem<'a> {
+    pub fn new() -> Self {
+        Self {
+            decoder: Decoder::V1,
+            bitmask: None,
+            memory: HashMap::new(),
+            floating: Vec::new(),
+        }
+    }
+
+    pub fn with_decoder(mut self, decoder: Decoder) -> Self {
+        self.decoder = decoder;
+        self
+    }
+
+    pub fn execute(&mut self, instruction: &'a Instruction<'a>) {
+        match instruction {
+            Instruction::BitMask(mask) => {
+                self.bitmask = Some(mask);
+            }
+            Instruction::MemWrite(address, value) => match self.decoder {
+                Decoder::V1 => self.write_v1(*address, *value),
+                Decoder::V2 => self.write_v2(*address, *value),
+            },
+        }
+    }
+
+    fn write_v1(&mut self, address: Address, value: Value) {
+        match &self.bitmask {
+            Some(mask) => {
+                self.memory.insert(address, mask.apply(value));
+            }
+            None => {
+                self.memory.insert(address, value);
+            }
+        }
+    }
+
+    /// Carves the new addresses out of every earlier write rather than
+    /// listing them, which could mean 2^36 of them.
+    fn write_v2(&mut self, address: Address, value: Value) {
+        let target = match &self.bitmask {
+            Some(mask) => mask.floating_address(address),
+            None => FloatingAddress::exact(address),
+        };
+
+        let mut floating = Vec::with_capacity(self.floating.len() + 1);
+        for (earlier, earlier_value) in self.floating.drain(..) {
+            floating.extend(
+                earlier
+                    .difference(target)
+                    .into_iter()
+                    .map(|rest| (rest, earlier_value)),
+            );
+        }
+        floating.push((target, value));
+        self.floating = floating;
+    }
+
+    /// The value at an address, which is 0 if it has never been written.
+    pub fn read(&self, address: Address) -> Value {
+        match self.decoder {
+            Decoder::V1 => self.memory.get(&address).copied().unwrap_or(0),
+            Decoder::V2 => self
+                .floating
+                .iter()
+                .find(|(pattern, _)| pattern.contains(address))
+                .map_or(0, |&(_, value)| value),
+        }
+    }
+
+    /// Wider than a `Value`, since a single write can fill 2^36 addresses.
+    pub fn sum_memory(&self) -> u128 {
+        let written = self.memory.values().map(|&value| u128::from(value));
+        let floating = self
+            .floating
+            .iter()
+            .map(|(address, value)| u128::from(address.count()) * u128::from(*value));
+        written.chain(floating).sum()
+    }
+}
+
+/// A set of addresses that agree on every bit outside `floating`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatingAddress {
+    /// The bits every address shares; floating bits are always 0 here.
+    fixed: Address,
+    floating: Address,
+}
+
+impl FloatingAddress {
+    pub fn exact(address: Address) -> Self {
+        Self {
+            fixed: address,
+            floating: 0,
+        }
+    }
+
+    /// How many addresses there are.
+    pub fn count(&self) -> u64 {
+        1 << self.floating.count_ones()
+    }
+
+    pub fn contains(&self, address: Address) -> bool {
+        address & !self.floating == self.fixed
+    }
+
+    /// Every address, lowest first.
+    pub fn addresses(&self) -> impl Iterator<Item = Address> {
+        let Self { fixed, floating } = *self;
+        std::iter::successors(Some(0), move |&bits: &Address| {
+            let next = bits.wrapping_sub(floating) & floating;
+            if next == 0 {
+                None
+            } else {
+                Some(next)
+            }
+        })
+        .map(move |bits| fixed | bits)
+    }
+
+    /// The addresses in `self` but not in `other`, as at most one set per
+    /// bit that floats here but not in `other`.
+    pub fn difference(self, other: Self) -> Vec<Self> {
+        let shared = !self.floating & !other.floating;
+        if self.fixed & shared != other.fixed & shared {
+            return vec![self];
+        }
+
+        let mut rest = Vec::new();
+        let mut remaining = self;
+        let mut bits = self.floating & !other.floating;
+        while bits != 0 {
+            let bit = bits & bits.wrapping_neg();
+            bits &= !bit;
+            remaining.floating &= !bit;
+            // the half that disagrees with `other` on this bit can't overlap
+            rest.push(Self {
+                fixed: remaining.fixed | (!other.fixed & bit),
+                floating: remaining.floating,
+            });
+            remaining.fixed |= other.fixed & bit;
+        }
+        rest
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Instruction<'a> {
+    BitMask(BitMask<'a>),
+    MemWrite(Address, Value),
+}
+
+impl<'a> TryFrom<&'a str> for Instruction<'a> {
+    type Error = Error;
+
+    fn try_from(s: &'a str) -> Result<Self> {
+        let i = s
+            .find(" = ")
+            .ok_or_else(|| Error::parse(1, "expected \"<target> = <value>\""))?;
+        let (first, second) = (&s[..i], &s[i + 3..]);
+        let column = column(s, i + 3);
+        if first == "mask" {
+            BitMask::try_from(second)
+                .map(Self::BitMask)
+                .map_err(|e| e.offset(0, column - 1))
+        } else if first.starts_with("mem[") && first.ends_with(']') {
+            let address = parse_number(&first[4..first.len() - 1], 5)?;
+            let value = parse_number(second, column)?;
+            Ok(Self::MemWrite(address, value))
+        } else {
+            Err(Error::unexpected(1, first, "mask or mem[<address>]"))
+        }
+    }
+}
+
+pub const BITMASK_LEN: usize = 36;
+
+#[derive(Debug, PartialEq)]
+pub struct BitMask<'a> {
+    mask: &'a str,
+}
+
+impl<'a> TryFrom<&'a str> for BitMask<'a> {
+    type Error = Error;
+
+    fn try_from(s: &'a str) -> Result<Self> {
+        if let Some((i, c)) = s
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !matches!(c, '0' | '1' | 'X'))
+        {
+            return Err(Error::unexpected(i + 1, c, "'0', '1' or 'X'"));
+        }
+        if s.len() != BITMASK_LEN {
+            let message = format!("bitmask has {} bits, expected {}", s.len(), BITMASK_LEN);
+            return Err(Error::parse(1, message));
+        }
+        Ok(Self { mask: s })
+    }
+}
+
+impl<'a> BitMask<'a> {
+    /// a 0 or 1 overwrites the corresponding bit in the value, while an X leaves the bit in the value unchanged.
+    pub fn apply(&self, value: u64) -> u64 {
+        let mut output = value;
+        for (i, c) in self.mask.chars().enumerate() {
+            match c {
+                '0' => {
+                    output &= !(1 << (BITMASK_LEN - i - 1));
+                }
+                '1' => {
+                    output |= 1 << (BITMASK_LEN - i - 1);
+                }
+                _ => {}
+            }
+        }
+        output
+    }
+
+    /// The bits set to `c` in the mask.
+    fn bits(&self, c: char) -> u64 {
+        self.mask
+            .chars()
+            .fold(0, |bits, m| (bits << 1) | u64::from(m == c))
+    }
+
+    /// a 1 overwrites the corresponding bit in the address, a 0 leaves it
+    /// unchanged and an X makes it floating, taking both values.
+    pub fn floating_address(&self, address: Address) -> FloatingAddress {
+        let floating = self.bits('X');
+        FloatingAddress {
+            fixed: (address | self.bits('1')) & !floating,
+            floating,
+        }
+    }
+
+    /// Every address the version 2 decoder writes to, lowest first.
+    pub fn apply_v2(&self, address: Address) -> Vec<Address> {
+        self.floating_address(address).addresses().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testing::Lcg;
+
+    #[test]
+    fn test_instruction_from() {
+        assert_eq!(
+            Instruction::try_from("mask = X111000X0101100001000000100011X0000X").unwrap(),
+            Instruction::BitMask(
+                BitMask::try_from("X111000X0101100001000000100011X0000X").unwrap()
+            )
+        );
+
+        assert_eq!(
+            Instruction::try_from("mem[4812] = 133322396").unwrap(),
+            Instruction::MemWrite(4812, 133322396)
+        );
+    }
+
+    #[test]
+    fn test_instruction_from_errors() {
+        assert_eq!(
+            Instruction::try_from("mask = X111000X0101100001000000100011X000")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 8: bitmask has 34 bits, expected 36"
+        );
+        assert_eq!(
+            Instruction::try_from("mask = X111000X0101100001000000100011X0002")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 42: expected '0', '1' or 'X', found \"2\""
+        );
+        assert!(Instruction::try_from("mem[48x] = 1").is_err());
+        assert!(Instruction::try_from("mem[48] = ").is_err());
+        assert!(Instruction::try_from("memory[48] = 1").is_err());
+        assert!(Instruction::try_from("mask X").is_err());
+    }
+
+    #[test]
+    fn test_bitmask_apply() {
+        let bitmask = BitMask::try_from("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X").unwrap();
+        assert_eq!(bitmask.apply(11), 73);
+        assert_eq!(bitmask.apply(101), 101);
+        assert_eq!(bitmask.apply(0), 64);
+    }
+
+    #[test]
+    fn test_bitmask_apply_v2() {
+        let bitmask = BitMask::try_from("000000000000000000000000000000X1001X").unwrap();
+        assert_eq!(bitmask.apply_v2(42), vec![26, 27, 58, 59]);
+
+        let bitmask = BitMask::try_from("00000000000000000000000000000000X0XX").unwrap();
+        assert_eq!(bitmask.apply_v2(26), vec![16, 17, 18, 19, 24, 25, 26, 27]);
+    }
+
+    #[test]
+    fn test_difference() {
+        let pattern = |s: &str| BitMask::try_from(s).unwrap().floating_address(0);
+        let all = pattern("000000000000000000000000000000000XXX");
+        let some = pattern("000000000000000000000000000000000X01");
+
+        let rest = all.difference(some);
+        assert_eq!(rest.iter().map(FloatingAddress::count).sum::<u64>(), 6);
+        let mut addresses: Vec<_> = rest.iter().flat_map(|p| p.addresses()).collect();
+        addresses.sort_unstable();
+        assert_eq!(addresses, [0, 2, 3, 4, 6, 7]);
+
+        // nothing in common
+        let other = pattern("000000000000000000000000000000001XXX");
+        assert_eq!(all.difference(other), [all]);
+        assert!(some.difference(all).is_empty());
+    }
+
+    #[test]
+    fn test_part2() {
+        let program = Day14
+            .parse(
+                "mask = 000000000000000000000000000000X1001X\n\
+                 mem[42] = 100\n\
+                 mask = 00000000000000000000000000000000X0XX\n\
+                 mem[26] = 1",
+            )
+            .unwrap();
+        assert_eq!(Day14.part2(&program), Some("208".to_owned()));
+
+        let mut computer = ComputerSystem::new().with_decoder(Decoder::V2);
+        program.iter().for_each(|i| computer.execute(i));
+        assert_eq!(computer.read(59), 100);
+        assert_eq!(computer.read(27), 1);
+        assert_eq!(computer.read(42), 0);
+    }
+
+    #[test]
+    fn test_many_floating_bits() {
+        let program = Day14
+            .parse(
+                "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX0000\n\
+                 mem[0] = 3\n\
+                 mask = 0XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX0000\n\
+                 mem[0] = 1\n\
+                 mask = 000000000000000000000000000000000000\n\
+                 mem[16] = 5",
+            )
+            .unwrap();
+        let mut computer = ComputerSystem::new().with_decoder(Decoder::V2);
+        program.iter().for_each(|i| computer.execute(i));
+        // 2^31 addresses hold 3, 2^31 - 1 hold 1 and one holds 5
+        assert_eq!(computer.sum_memory(), (3 << 31) + (1 << 31) - 1 + 5);
+        assert_eq!(computer.read(1 << 35), 3);
+        assert_eq!(computer.read(16), 5);
+
+        // 2^30 copies of the largest 36-bit value overflow a u64
+        let program = Day14
+            .parse(
+                "mask = 000000XXXXXXXXXXXXXXXXXXXXXXXXXXXXXX\n\
+                 mem[0] = 68719476735",
+            )
+            .unwrap();
+        assert_eq!(
+            Day14.part2(&program),
+            Some((68719476735_u128 << 30).to_string())
+        );
+    }
+
+    #[test]
+    fn test_matches_listed_addresses() {
+        let mut lcg = Lcg::new(14);
+        let mut random = move |n: usize| lcg.below(n);
+
+        // masks only float the low bits so every address can be listed
+        let masks: Vec<String> = (0..20)
+            .map(|_| {
+                let low: String = (0..6).map(|_| ['0', '1', 'X'][random(3)]).collect();
+                format!("{}{}", "0".repeat(BITMASK_LEN - 6), low)
+            })
+            .collect();
+        let mut text = String::new();
+        for mask in &masks {
+            text += &format!("mask = {}\n", mask);
+            for _ in 0..random(4) {
+                text += &format!("mem[{}] = {}\n", random(64), random(1000));
+            }
+        }
+        let program = Day14.parse(&text).unwrap();
+
+        let mut computer = ComputerSystem::new().with_decoder(Decoder::V2);
+        let mut listed = HashMap::new();
+        let mut mask = None;
+        for instruction in &program {
+            computer.execute(instruction);
+            match instruction {
+                Instruction::BitMask(m) => mask = Some(m),
+                Instruction::MemWrite(address, value) => {
+                    for a in mask.unwrap().apply_v2(*address) {
+                        listed.insert(a, *value);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(
+            computer.sum_memory(),
+            listed.values().map(|&v| u128::from(v)).sum::<u128>()
+        );
+        for address in 0..64 {
+            assert_eq!(
+                computer.read(address),
+                listed.get(&address).copied().unwrap_or(0)
+            );
+        }
+    }
+}
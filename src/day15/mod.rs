@@ -1,32 +1,38 @@
-use input;
+use crate::{Error, Result, Solution};
 
 use std::collections::HashMap;
-use std::io;
+use std::str::FromStr;
 
-fn main() -> io::Result<()> {
-    let contents = input::load_file_by_lines("src/day15/input.txt")?;
+pub struct Day15;
 
-    let mut game = MemoryGame::from(contents.iter().nth(0).unwrap().as_ref());
+impl Solution for Day15 {
+    type Input<'a> = MemoryGame;
 
-    game.take_turns(2020);
-
-    println!("part1: {}", game.last_number());
-
-    game.take_turns(30_000_000);
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        text.lines().next().unwrap_or_default().parse()
+    }
 
-    println!("part2: {}", game.last_number());
+    fn part1(&self, game: &Self::Input<'_>) -> Option<String> {
+        let mut game = game.clone();
+        game.take_turns(2020);
+        Some(game.last_number().to_string())
+    }
 
-    Ok(())
+    fn part2(&self, game: &Self::Input<'_>) -> Option<String> {
+        let mut game = game.clone();
+        game.take_turns(30_000_000);
+        Some(game.last_number().to_string())
+    }
 }
 
-#[derive(Debug)]
-struct MemoryGame {
+#[derive(Clone, Debug)]
+pub struct MemoryGame {
     numbers: Vec<usize>,
     last: HashMap<usize, usize>,
 }
 
 impl MemoryGame {
-    fn do_turn(&mut self) {
+    pub fn do_turn(&mut self) {
         let previous_turn = self.numbers.len();
         let last_number = self.numbers.last().expect("no numbers in the memory game");
         // have we seen the last number before?
@@ -38,21 +44,20 @@ impl MemoryGame {
         self.numbers.push(next);
     }
 
-    fn take_turns(&mut self, num_turns: usize) {
+    pub fn take_turns(&mut self, num_turns: usize) {
         (self.numbers.len()..num_turns).for_each(|_| self.do_turn());
     }
 
-    fn last_number(&self) -> usize {
+    pub fn last_number(&self) -> usize {
         *self.numbers.last().expect("game has no numbers")
     }
 }
 
-impl From<&str> for MemoryGame {
-    fn from(s: &str) -> Self {
-        let numbers: Vec<usize> = s
-            .split(",")
-            .map(|x| x.parse::<usize>().expect("failed to parse a number"))
-            .collect();
+impl FromStr for MemoryGame {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let numbers: Vec<usize> = crate::parse_separated(s, ',')?;
 
         let mut last = HashMap::new();
         // record indices for all but the last number.
@@ -60,7 +65,7 @@ impl From<&str> for MemoryGame {
             last.insert(numbers[i], i + 1);
         });
 
-        Self { numbers, last }
+        Ok(Self { numbers, last })
     }
 }
 
@@ -70,14 +75,14 @@ mod tests {
 
     #[test]
     fn test_memorygame_from() {
-        let game = MemoryGame::from("0,3,6");
+        let game = "0,3,6".parse::<MemoryGame>().unwrap();
 
         assert_eq!(game.numbers, vec![0, 3, 6]);
     }
 
     #[test]
     fn test_memorygame_doturn() {
-        let mut game = MemoryGame::from("0,3,6");
+        let mut game = "0,3,6".parse::<MemoryGame>().unwrap();
 
         game.do_turn();
 
@@ -94,7 +99,7 @@ mod tests {
 
     #[test]
     fn test_memorygame_taketurns() {
-        let mut game = MemoryGame::from("0,3,6");
+        let mut game = "0,3,6".parse::<MemoryGame>().unwrap();
 
         game.take_turns(10);
 
@@ -103,10 +108,19 @@ mod tests {
 
     #[test]
     fn test_memorygame_2020_number() {
-        let mut game = MemoryGame::from("0,3,6");
+        let mut game = "0,3,6".parse::<MemoryGame>().unwrap();
 
         game.take_turns(2020);
 
         assert_eq!(*game.numbers.last().unwrap(), 436);
     }
+
+    #[test]
+    fn test_memorygame_from_errors() {
+        assert_eq!(
+            "0,3,x".parse::<MemoryGame>().unwrap_err().to_string(),
+            "line 1, column 5: invalid digit found in string in \"x\""
+        );
+        assert!("".parse::<MemoryGame>().is_err());
+    }
 }
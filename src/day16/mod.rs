@@ -0,0 +1,472 @@
+use crate::constraint::{Candidates, Unsolvable};
+use crate::error::{column, parse_number};
+use crate::record::{self, Record};
+use crate::{Error, Result, Solution};
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = Notes<'a>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        Notes::try_from(text)
+    }
+
+    fn part1(&self, notes: &Self::Input<'_>) -> Option<String> {
+        Some(notes.ticket_scanning_error_rate().to_string())
+    }
+
+    fn part2(&self, notes: &Self::Input<'_>) -> Option<String> {
+        notes.departure_product().map(|p| p.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Rule<'a> {
+    field_name: &'a str,
+    ranges: (RangeInclusive<usize>, RangeInclusive<usize>),
+}
+
+impl<'a> Rule<'a> {
+    pub fn is_valid_value(&self, value: usize) -> bool {
+        self.ranges.0.contains(&value) || self.ranges.1.contains(&value)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Rule<'a> {
+    type Error = Error;
+
+    /// e.g. `departure location: 36-363 or 377-962`
+    fn try_from(s: &'a str) -> Result<Self> {
+        let colon = s
+            .find(": ")
+            .ok_or_else(|| Error::parse(1, "expected \"<field>: <range> or <range>\""))?;
+        let field_name = &s[..colon];
+        let ranges = &s[colon + 2..];
+        let or = ranges
+            .find(" or ")
+            .ok_or_else(|| Error::parse(column(s, colon + 2), "expected \"<range> or <range>\""))?;
+
+        let first = parse_range(&ranges[..or], column(s, colon + 2))?;
+        let second = parse_range(&ranges[or + 4..], column(s, colon + or + 6))?;
+
+        Ok(Self {
+            field_name,
+            ranges: (first, second),
+        })
+    }
+}
+
+fn parse_range(s: &str, column: usize) -> Result<RangeInclusive<usize>> {
+    let hyphen = s
+        .find('-')
+        .ok_or_else(|| Error::parse(column, "expected \"<low>-<high>\""))?;
+    let low = parse_number(&s[..hyphen], column)?;
+    let high = parse_number(&s[hyphen + 1..], column + s[..=hyphen].chars().count())?;
+    Ok(low..=high)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Ticket(Vec<usize>);
+
+impl Ticket {
+    /// The values that no rule allows.
+    pub fn invalid_values<'t>(&'t self, rules: &'t [Rule]) -> impl Iterator<Item = usize> + 't {
+        self.0
+            .iter()
+            .copied()
+            .filter(move |&value| !rules.iter().any(|r| r.is_valid_value(value)))
+    }
+
+    /// Whether every value is allowed by some rule.
+    pub fn is_valid(&self, rules: &[Rule]) -> bool {
+        self.invalid_values(rules).next().is_none()
+    }
+
+    pub fn values(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl FromStr for Ticket {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        crate::parse_separated(s, ',').map(Self)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Notes<'a> {
+    rules: Vec<Rule<'a>>,
+    your: Ticket,
+    nearby: Vec<Ticket>,
+}
+
+impl<'a> Notes<'a> {
+    pub fn ticket_scanning_error_rate(&self) -> usize {
+        self.nearby
+            .iter()
+            .flat_map(|t| t.invalid_values(&self.rules))
+            .sum()
+    }
+
+    pub fn valid_for_any_field(&self, value: usize) -> bool {
+        self.rules.iter().any(|r| r.is_valid_value(value))
+    }
+
+    /// Nearby tickets with no value that the ticket scanning error rate
+    /// counts.
+    pub fn valid_tickets(&self) -> impl Iterator<Item = &Ticket> {
+        self.nearby.iter().filter(move |t| t.is_valid(&self.rules))
+    }
+
+    /// Which column of the tickets each field is in, worked out by ruling out
+    /// columns where a valid nearby ticket has a value the field's rule
+    /// doesn't allow.
+    pub fn field_columns(
+        &self,
+    ) -> std::result::Result<BTreeMap<&'a str, usize>, Unsolvable<&'a str, usize>> {
+        let mut candidates = Candidates::new();
+        for rule in &self.rules {
+            candidates.restrict(rule.field_name, 0..self.your.0.len());
+        }
+        for ticket in self.valid_tickets() {
+            for (column, &value) in ticket.0.iter().enumerate() {
+                for rule in self.rules.iter().filter(|r| !r.is_valid_value(value)) {
+                    candidates.exclude(&rule.field_name, &column);
+                }
+            }
+        }
+        candidates.solve()
+    }
+
+    /// The product of the fields on your ticket whose names start with
+    /// "departure", or `None` if the columns can't be worked out or there are
+    /// no such fields.
+    pub fn departure_product(&self) -> Option<usize> {
+        let columns = self.field_columns().ok()?;
+        let departures: Vec<usize> = columns
+            .iter()
+            .filter(|(name, _)| name.starts_with("departure"))
+            .map(|(_, &column)| self.your.0[column])
+            .collect();
+        if departures.is_empty() {
+            return None;
+        }
+        Some(departures.iter().product())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Notes<'a> {
+    type Error = Error;
+
+    /// The rules, your ticket and nearby tickets are separated by blank lines.
+    fn try_from(s: &'a str) -> Result<Self> {
+        let records = record::split(s);
+        let (rules, your, nearby) = match records.as_slice() {
+            [rules, your, nearby] => (rules, your, nearby),
+            [] | [_] | [_, _] => {
+                return Err(Error::parse(
+                    1,
+                    "expected rules, your ticket and nearby tickets",
+                ))
+            }
+            [_, _, _, extra, ..] => {
+                return Err(Error::parse(1, "unexpected section after nearby tickets")
+                    .offset(extra.line() - 1, 0))
+            }
+        };
+
+        let rules = rules.parse_lines_with(Rule::try_from)?;
+
+        let mut your_tickets = ticket_section(your, "your ticket:")?;
+        if your_tickets.len() > 1 {
+            let line = your.lines()[2];
+            return Err(line.locate(Error::parse(1, "expected only one ticket")));
+        }
+        let your = your_tickets
+            .pop()
+            .ok_or_else(|| Error::parse(1, "missing your ticket").offset(your.line(), 0))?;
+
+        let nearby_tickets = ticket_section(nearby, "nearby tickets:")?;
+        let columns = your.0.len();
+        if let Some(i) = nearby_tickets.iter().position(|t| t.0.len() != columns) {
+            let message = format!("expected {} values like your ticket", columns);
+            return Err(nearby.lines()[i + 1].locate(Error::parse(1, message)));
+        }
+        let nearby = nearby_tickets;
+
+        Ok(Self {
+            rules,
+            your,
+            nearby,
+        })
+    }
+}
+
+/// Parses a header line followed by one ticket per line.
+fn ticket_section(record: &Record, header: &str) -> Result<Vec<Ticket>> {
+    let (first, tickets) = record
+        .lines()
+        .split_first()
+        .expect("records are never empty");
+    if first.text != header {
+        return Err(first.locate(Error::unexpected(1, first.text, format!("{:?}", header))));
+    }
+
+    tickets
+        .iter()
+        .map(|line| line.parse_with(str::parse))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_from() {
+        let rule = Rule::try_from("class: 1-3 or 5-7").unwrap();
+
+        assert_eq!(
+            rule,
+            Rule {
+                field_name: "class",
+                ranges: (1..=3, 5..=7)
+            }
+        );
+
+        let rule = Rule::try_from("row: 6-11 or 33-44").unwrap();
+
+        assert_eq!(
+            rule,
+            Rule {
+                field_name: "row",
+                ranges: (6..=11, 33..=44)
+            }
+        );
+
+        let rule = Rule::try_from("seat: 13-40 or 45-50").unwrap();
+
+        assert_eq!(
+            rule,
+            Rule {
+                field_name: "seat",
+                ranges: (13..=40, 45..=50)
+            }
+        );
+
+        let rule = Rule::try_from("departure location: 36-363 or 377-962").unwrap();
+
+        assert_eq!(
+            rule,
+            Rule {
+                field_name: "departure location",
+                ranges: (36..=363, 377..=962)
+            }
+        );
+    }
+
+    #[test]
+    fn test_ticket_from() {
+        let ticket: Ticket = "7,1,14".parse().unwrap();
+
+        assert_eq!(ticket.0, vec![7, 1, 14]);
+    }
+
+    #[test]
+    fn test_notes_from() {
+        let notes = Notes::try_from(
+            "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12",
+        )
+        .unwrap();
+
+        assert_eq!(notes.your.0, vec![7, 1, 14]);
+    }
+
+    #[test]
+    fn test_notes_from_crlf() {
+        let notes = "class: 1-3 or 5-7\r\n\r\nyour ticket:\r\n7,1,14\r\n\r\n\
+                     nearby tickets:\r\n7,3,47\r\n40,4,50\r\n\r\n";
+        let notes = Notes::try_from(notes).unwrap();
+
+        assert_eq!(notes.your.0, vec![7, 1, 14]);
+        assert_eq!(notes.nearby.len(), 2);
+    }
+
+    #[test]
+    fn test_notes_ticket_scanning_error_rate() {
+        let notes = Notes::try_from(
+            "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12",
+        )
+        .unwrap();
+
+        assert_eq!(notes.ticket_scanning_error_rate(), 71);
+        assert_eq!(notes.valid_tickets().count(), 1);
+    }
+
+    #[test]
+    fn test_ticket_is_valid() {
+        let rules = vec![
+            Rule::try_from("class: 1-3 or 5-7").unwrap(),
+            Rule::try_from("row: 6-11 or 33-44").unwrap(),
+        ];
+        let ticket: Ticket = "7,3,47".parse().unwrap();
+        assert!(!ticket.is_valid(&rules));
+        assert_eq!(ticket.invalid_values(&rules).collect::<Vec<_>>(), [47]);
+
+        // Each value only needs to match some rule, not the same one.
+        let ticket: Ticket = "2,40".parse().unwrap();
+        assert!(ticket.is_valid(&rules));
+    }
+
+    #[test]
+    fn test_field_columns() {
+        let notes = Notes::try_from(
+            "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9
+20,20,20",
+        )
+        .unwrap();
+
+        let columns = notes.field_columns().unwrap();
+        assert_eq!(
+            columns.into_iter().collect::<Vec<_>>(),
+            [("class", 1), ("row", 0), ("seat", 2)]
+        );
+        assert_eq!(notes.departure_product(), None);
+        assert_eq!(Day16.part2(&notes), None);
+    }
+
+    #[test]
+    fn test_departure_product() {
+        let notes = Notes::try_from(
+            "departure track: 0-1 or 4-19
+row: 0-5 or 8-19
+departure time: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9",
+        )
+        .unwrap();
+
+        assert_eq!(notes.departure_product(), Some(12 * 13));
+        assert_eq!(Day16.part2(&notes), Some("156".to_owned()));
+
+        let notes = Notes::try_from(
+            "departure track: 0-19 or 30-40
+departure time: 0-19 or 30-40
+
+your ticket:
+1,2
+
+nearby tickets:
+3,4",
+        )
+        .unwrap();
+        assert_eq!(
+            notes.field_columns().unwrap_err().to_string(),
+            "more than one candidate left for departure time (0, 1); \
+             departure track (0, 1)"
+        );
+        assert_eq!(notes.departure_product(), None);
+    }
+
+    #[test]
+    fn test_notes_from_errors() {
+        let notes = "class: 1-3 or 5-7
+row: 6-11 or 33-44
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,x,50";
+        assert_eq!(
+            Notes::try_from(notes).unwrap_err().to_string(),
+            "line 9, column 4: invalid digit found in string in \"x\""
+        );
+
+        let notes = "class: 1-3 or 5-7
+row: 6-11 33-44
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47";
+        assert_eq!(
+            Notes::try_from(notes).unwrap_err().to_string(),
+            "line 2, column 6: expected \"<range> or <range>\""
+        );
+
+        let notes = "class: 1-3 or 5-7
+
+my ticket:
+7,1,14
+
+nearby tickets:
+7,3,47";
+        assert_eq!(
+            Notes::try_from(notes).unwrap_err().to_string(),
+            "line 3, column 1: expected \"your ticket:\", found \"my ticket:\""
+        );
+
+        assert!(Notes::try_from("class: 1-3 or 5-7").is_err());
+
+        let notes = "class: 1-3 or 5-7\n\nyour ticket:\n\nnearby tickets:\n7,3,47";
+        assert_eq!(
+            Notes::try_from(notes).unwrap_err().to_string(),
+            "line 4, column 1: missing your ticket"
+        );
+
+        let notes = "class: 1-3 or 5-7\n\nyour ticket:\n7,1\n\nnearby tickets:\n7,3\n7,3,47";
+        assert_eq!(
+            Notes::try_from(notes).unwrap_err().to_string(),
+            "line 8, column 1: expected 2 values like your ticket"
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{Error, Result, Solution};
+
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = PocketDimension<2>;
+
+    /// The initial slice, which every dimension above the second extends at 0.
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        text.parse()
+    }
+
+    fn part1(&self, slice: &Self::Input<'_>) -> Option<String> {
+        let mut pocket_dimension = slice.extend::<3>();
+        pocket_dimension.boot();
+        Some(pocket_dimension.active_cubes().to_string())
+    }
+
+    fn part2(&self, slice: &Self::Input<'_>) -> Option<String> {
+        let mut pocket_dimension = slice.extend::<4>();
+        pocket_dimension.boot();
+        Some(pocket_dimension.active_cubes().to_string())
+    }
+}
+
+/// `x`, `y`, `z`, then any further dimensions.
+pub type Coordinate<const D: usize> = [isize; D];
+
+/// An infinite grid of cubes in `D` dimensions, stored as the cubes that are
+/// active.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PocketDimension<const D: usize> {
+    active: HashSet<Coordinate<D>>,
+}
+
+impl<const D: usize> PocketDimension<D> {
+    pub fn boot(&mut self) {
+        (0..BOOT_CYCLES).for_each(|_| self.cycle());
+    }
+
+    pub fn active_cubes(&self) -> usize {
+        self.active.len()
+    }
+
+    /// The same cubes in `E` dimensions, at 0 in every dimension added.
+    ///
+    /// # Panics
+    ///
+    /// If `E` is less than `D`.
+    pub fn extend<const E: usize>(&self) -> PocketDimension<E> {
+        assert!(E >= D, "cannot fit {} dimensions into {}", D, E);
+        let active = self
+            .active
+            .iter()
+            .map(|coordinate| {
+                let mut extended = [0; E];
+                extended[..D].copy_from_slice(coordinate);
+                extended
+            })
+            .collect();
+        PocketDimension { active }
+    }
+
+    /// If a cube is active and exactly 2 or 3 of its neighbors are also active, the cube remains active. Otherwise, the cube becomes inactive.
+    /// If a cube is inactive but exactly 3 of its neighbors are active, the cube becomes active. Otherwise, the cube remains inactive.
+    ///
+    /// Only cubes next to an active one can be active afterwards, so the
+    /// dimension grows by at most one cube in each direction per cycle.
+    pub fn cycle(&mut self) {
+        let mut active_neighbors: HashMap<Coordinate<D>, usize> = HashMap::new();
+        for &coordinate in &self.active {
+            for neighbor in neighbors(coordinate) {
+                *active_neighbors.entry(neighbor).or_default() += 1;
+            }
+        }
+
+        self.active = active_neighbors
+            .into_iter()
+            .filter(|&(coordinate, count)| {
+                count == 3 || (count == 2 && self.active.contains(&coordinate))
+            })
+            .map(|(coordinate, _)| coordinate)
+            .collect();
+    }
+
+    pub fn num_active_neighbors(&self, coordinate: Coordinate<D>) -> usize {
+        neighbors(coordinate)
+            .filter(|neighbor| self.active.contains(neighbor))
+            .count()
+    }
+
+    pub fn get(&self, coordinate: Coordinate<D>) -> Cube {
+        if self.active.contains(&coordinate) {
+            Cube::Active
+        } else {
+            Cube::Inactive
+        }
+    }
+}
+
+/// The `3^D - 1` coordinates that differ from `coordinate` by at most 1 in
+/// each dimension.
+fn neighbors<const D: usize>(coordinate: Coordinate<D>) -> impl Iterator<Item = Coordinate<D>> {
+    let count = 3_usize.pow(D as u32);
+    (0..count).filter(move |&i| i != count / 2).map(move |i| {
+        let mut neighbor = coordinate;
+        let mut digits = i;
+        for c in neighbor.iter_mut() {
+            *c += (digits % 3) as isize - 1;
+            digits /= 3;
+        }
+        neighbor
+    })
+}
+
+pub const BOOT_CYCLES: usize = 6;
+
+impl<const D: usize> FromStr for PocketDimension<D> {
+    type Err = Error;
+
+    /// A slice through `x` and `y` with `x` along each line, at 0 in every
+    /// other dimension.
+    ///
+    /// # Panics
+    ///
+    /// If `D` is less than 2.
+    fn from_str(s: &str) -> Result<Self> {
+        assert!(D >= 2, "a slice needs at least 2 dimensions, not {}", D);
+        let mut active = HashSet::new();
+
+        for (y, line) in s.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if Cube::try_from(c).map_err(|e| e.offset(y, x))? == Cube::Active {
+                    let mut coordinate = [0; D];
+                    coordinate[0] = x as isize;
+                    coordinate[1] = y as isize;
+                    active.insert(coordinate);
+                }
+            }
+        }
+
+        Ok(Self { active })
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Cube {
+    Active,
+    Inactive,
+}
+
+impl TryFrom<char> for Cube {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            '#' => Ok(Self::Active),
+            '.' => Ok(Self::Inactive),
+            c => Err(Error::unexpected(1, c, "'#' or '.'")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = ".#.
+..#
+###";
+
+    #[test]
+    fn test_cube_from() {
+        assert_eq!(Cube::try_from('#').unwrap(), Cube::Active);
+        assert_eq!(Cube::try_from('.').unwrap(), Cube::Inactive);
+    }
+
+    #[test]
+    fn test_cube_from_error() {
+        assert!(Cube::try_from(' ').is_err());
+        assert_eq!(
+            ".#\n#?"
+                .parse::<PocketDimension<3>>()
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 2: expected '#' or '.', found \"?\""
+        );
+    }
+
+    #[test]
+    fn test_pocket_dimension() {
+        let pocket_dimension = PocketDimension::from_str(".#").unwrap();
+        assert_eq!(pocket_dimension.get([0, 0, 0]), Cube::Inactive);
+        assert_eq!(pocket_dimension.get([1, 0, 0]), Cube::Active);
+    }
+
+    #[test]
+    fn test_pocket_dimension_active_cubes() {
+        let pocket_dimension = PocketDimension::<3>::from_str("##.##").unwrap();
+        assert_eq!(pocket_dimension.active_cubes(), 4);
+    }
+
+    #[test]
+    fn test_pocket_dimension_num_active_neighbors() {
+        let pocket_dimension = PocketDimension::from_str("##.##").unwrap();
+        assert_eq!(pocket_dimension.num_active_neighbors([0, 0, 0]), 1);
+        assert_eq!(pocket_dimension.num_active_neighbors([2, 1, -1]), 2);
+
+        let pocket_dimension = PocketDimension::from_str(EXAMPLE).unwrap();
+        assert_eq!(pocket_dimension.num_active_neighbors([1, 1, 0]), 5);
+        let pocket_dimension = PocketDimension::<4>::from_str(EXAMPLE).unwrap();
+        assert_eq!(pocket_dimension.num_active_neighbors([1, 1, 0, 1]), 5);
+        assert_eq!(neighbors([0; 4]).count(), 80);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut pocket_dimension = PocketDimension::<3>::from_str(EXAMPLE).unwrap();
+        pocket_dimension.cycle();
+        // the puzzle shows the first cycle starting a row further up
+        let expected = [
+            [0, 1, -1],
+            [2, 2, -1],
+            [1, 3, -1],
+            [0, 1, 0],
+            [2, 1, 0],
+            [1, 2, 0],
+            [2, 2, 0],
+            [1, 3, 0],
+            [0, 1, 1],
+            [2, 2, 1],
+            [1, 3, 1],
+        ];
+        assert_eq!(pocket_dimension.active_cubes(), expected.len());
+        assert!(expected
+            .iter()
+            .all(|&c| pocket_dimension.get(c) == Cube::Active));
+    }
+
+    #[test]
+    fn test_part1() {
+        let mut pocket_dimension = PocketDimension::<3>::from_str(EXAMPLE).unwrap();
+
+        pocket_dimension.boot();
+
+        assert_eq!(pocket_dimension.active_cubes(), 112);
+    }
+
+    #[test]
+    fn test_part2() {
+        let slice = Day17.parse(EXAMPLE).unwrap();
+        assert_eq!(Day17.part1(&slice), Some("112".to_owned()));
+        assert_eq!(Day17.part2(&slice), Some("848".to_owned()));
+    }
+
+    #[test]
+    fn test_extend() {
+        let slice = PocketDimension::<2>::from_str(".#").unwrap();
+        let cube: PocketDimension<5> = slice.extend();
+        assert_eq!(cube.get([1, 0, 0, 0, 0]), Cube::Active);
+        assert_eq!(cube.active_cubes(), 1);
+
+        // a lone cube and anything else in 5 dimensions dies out
+        let mut cube = cube;
+        cube.cycle();
+        assert_eq!(cube.active_cubes(), 0);
+    }
+}
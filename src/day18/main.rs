@@ -1,19 +0,0 @@
-
-use input;
-
-use std::io;
-
-fn main() -> io::Result<()> {
-    let _contents = input::load_file_by_lines("src/day18/input.txt")?;
-
-    Ok(())
-}
-
-# [cfg(test)]
-mod tests {
-
-    # [test]
-    fn test() {
-
-    }
-}
@@ -0,0 +1,403 @@
+use crate::{Error, Result, Solution};
+
+use std::fmt;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = Vec<Vec<Token>>;
+
+    /// Tokenizes every line and checks it parses, so a malformed line is
+    /// reported with its position rather than as no answer.
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        crate::parse_lines_with(text, |line| {
+            let tokens = tokenize(line)?;
+            Expr::parse(&tokens, Precedence::EQUAL)?;
+            Ok(tokens)
+        })
+    }
+
+    fn part1(&self, homework: &Self::Input<'_>) -> Option<String> {
+        sum(homework, Precedence::EQUAL).map(|total| total.to_string())
+    }
+
+    fn part2(&self, homework: &Self::Input<'_>) -> Option<String> {
+        sum(homework, Precedence::ADDITION_FIRST).map(|total| total.to_string())
+    }
+}
+
+/// Evaluates every line of the homework with the given precedence and adds
+/// up the results, or returns `None` if a line is not an expression or any of
+/// that overflows.
+pub fn sum(homework: &[Vec<Token>], precedence: Precedence) -> Option<u64> {
+    homework.iter().try_fold(0_u64, |total, tokens| {
+        let value = Expr::parse(tokens, precedence).ok()?.eval()?;
+        total.checked_add(value)
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op {
+    Add,
+    Mul,
+}
+
+impl Op {
+    /// `None` if the result overflows.
+    pub fn apply(self, a: u64, b: u64) -> Option<u64> {
+        match self {
+            Self::Add => a.checked_add(b),
+            Self::Mul => a.checked_mul(b),
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Add => write!(f, "+"),
+            Self::Mul => write!(f, "*"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenKind {
+    Number(u64),
+    Op(Op),
+    Open,
+    Close,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{}", n),
+            Self::Op(op) => write!(f, "{}", op),
+            Self::Open => write!(f, "("),
+            Self::Close => write!(f, ")"),
+        }
+    }
+}
+
+/// A token and the 1-based column it starts at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub column: usize,
+}
+
+pub fn tokenize(line: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().enumerate().peekable();
+
+    while let Some((n, (i, c))) = chars.next() {
+        let column = n + 1;
+        let kind = match c {
+            ' ' | '\t' => continue,
+            '+' => TokenKind::Op(Op::Add),
+            '*' => TokenKind::Op(Op::Mul),
+            '(' => TokenKind::Open,
+            ')' => TokenKind::Close,
+            '0'..='9' => {
+                let mut end = i + 1;
+                while let Some(&(_, (j, '0'..='9'))) = chars.peek() {
+                    end = j + 1;
+                    chars.next();
+                }
+                TokenKind::Number(crate::error::parse_number(&line[i..end], column)?)
+            }
+            c => {
+                return Err(Error::unexpected(
+                    column,
+                    c,
+                    "a number, '+', '*', '(' or ')'",
+                ))
+            }
+        };
+        tokens.push(Token { kind, column });
+    }
+
+    Ok(tokens)
+}
+
+/// How tightly each operator binds; higher binds tighter. Operators with the
+/// same precedence are evaluated left to right.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Precedence {
+    pub add: u8,
+    pub mul: u8,
+}
+
+impl Precedence {
+    /// Part 1: `+` and `*` bind equally.
+    pub const EQUAL: Self = Self { add: 1, mul: 1 };
+
+    /// Part 2: `+` binds tighter than `*`.
+    pub const ADDITION_FIRST: Self = Self { add: 2, mul: 1 };
+
+    pub fn of(&self, op: Op) -> u8 {
+        match op {
+            Op::Add => self.add,
+            Op::Mul => self.mul,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Number(u64),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Parses a whole line of tokens with a Pratt parser.
+    pub fn parse(tokens: &[Token], precedence: Precedence) -> Result<Self> {
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            precedence,
+        };
+        let expr = parser.expr(0)?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(Error::unexpected(
+                token.column,
+                token.kind,
+                "an operator or end of line",
+            )),
+        }
+    }
+
+    /// `None` if any step overflows.
+    pub fn eval(&self) -> Option<u64> {
+        match self {
+            Self::Number(n) => Some(*n),
+            Self::Binary(op, a, b) => op.apply(a.eval()?, b.eval()?),
+        }
+    }
+}
+
+/// Writes every operation in parentheses, showing how it was grouped.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{}", n),
+            Self::Binary(op, a, b) => write!(f, "({} {} {})", a, op, b),
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    precedence: Precedence,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self, expected: &str) -> Result<Token> {
+        let token = self.peek().ok_or_else(|| {
+            let column = self.tokens.last().map_or(1, |t| t.column + 1);
+            Error::unexpected(column, "end of line", expected)
+        })?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    /// Parses operations binding at least as tightly as `min`.
+    fn expr(&mut self, min: u16) -> Result<Expr> {
+        let mut lhs = self.operand()?;
+
+        while let Some(Token {
+            kind: TokenKind::Op(op),
+            ..
+        }) = self.peek()
+        {
+            // Wider than a precedence, so one more than the highest fits.
+            let precedence = u16::from(self.precedence.of(op));
+            if precedence < min {
+                break;
+            }
+            self.position += 1;
+            // Only tighter operators may take the right-hand side, which
+            // makes equal ones associate to the left.
+            let rhs = self.expr(precedence + 1)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+
+        Ok(lhs)
+    }
+
+    fn operand(&mut self) -> Result<Expr> {
+        let token = self.next("a number or '('")?;
+        match token.kind {
+            TokenKind::Number(n) => Ok(Expr::Number(n)),
+            TokenKind::Open => {
+                let expr = self.expr(0)?;
+                match self.next("')'")? {
+                    Token {
+                        kind: TokenKind::Close,
+                        ..
+                    } => Ok(expr),
+                    t => Err(Error::unexpected(t.column, t.kind, "')'")),
+                }
+            }
+            kind => Err(Error::unexpected(token.column, kind, "a number or '('")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str, precedence: Precedence) -> Expr {
+        Expr::parse(&tokenize(line).unwrap(), precedence).unwrap()
+    }
+
+    fn error(line: &str) -> String {
+        tokenize(line)
+            .and_then(|tokens| Expr::parse(&tokens, Precedence::EQUAL))
+            .unwrap_err()
+            .to_string()
+    }
+
+    static EXAMPLES: &[(&str, u64, u64)] = &[
+        ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+        ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51),
+        ("2 * 3 + (4 * 5)", 26, 46),
+        ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
+        ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
+        (
+            "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+            13632,
+            23340,
+        ),
+    ];
+
+    #[test]
+    fn test_tokenize() {
+        let kinds: Vec<TokenKind> = tokenize("12*(3 +4)")
+            .unwrap()
+            .into_iter()
+            .map(|t| t.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                TokenKind::Number(12),
+                TokenKind::Op(Op::Mul),
+                TokenKind::Open,
+                TokenKind::Number(3),
+                TokenKind::Op(Op::Add),
+                TokenKind::Number(4),
+                TokenKind::Close
+            ]
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        for &(line, expected, _) in EXAMPLES {
+            assert_eq!(
+                parse(line, Precedence::EQUAL).eval(),
+                Some(expected),
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        for &(line, _, expected) in EXAMPLES {
+            let expr = parse(line, Precedence::ADDITION_FIRST);
+            assert_eq!(expr.eval(), Some(expected), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let line = "1 + 2 * 3 + 4";
+        assert_eq!(
+            parse(line, Precedence::EQUAL).to_string(),
+            "(((1 + 2) * 3) + 4)"
+        );
+        assert_eq!(
+            parse(line, Precedence::ADDITION_FIRST).to_string(),
+            "((1 + 2) * (3 + 4))"
+        );
+        let reversed = Precedence { add: 1, mul: 2 };
+        assert_eq!(parse(line, reversed).to_string(), "((1 + (2 * 3)) + 4)");
+        assert_eq!(parse("((7))", Precedence::EQUAL).to_string(), "7");
+        let highest = Precedence { add: 255, mul: 255 };
+        assert_eq!(parse(line, highest).to_string(), "(((1 + 2) * 3) + 4)");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            error("1 + x"),
+            "line 1, column 5: expected a number, '+', '*', '(' or ')', found \"x\""
+        );
+        assert_eq!(
+            error("1 + (2 * 3"),
+            "line 1, column 11: expected ')', found \"end of line\""
+        );
+        assert_eq!(
+            error("1 + 2)"),
+            "line 1, column 6: expected an operator or end of line, found \")\""
+        );
+        assert_eq!(
+            error("* 2"),
+            "line 1, column 1: expected a number or '(', found \"*\""
+        );
+        assert_eq!(
+            error("1 2"),
+            "line 1, column 3: expected an operator or end of line, found \"2\""
+        );
+        assert_eq!(
+            Day18.parse("1 + 2\n3 +").unwrap_err().to_string(),
+            "line 2, column 4: expected a number or '(', found \"end of line\""
+        );
+    }
+
+    #[test]
+    fn test_solution() {
+        let text: Vec<&str> = EXAMPLES.iter().map(|(line, _, _)| *line).collect();
+        let homework = Day18.parse(&text.join("\n")).unwrap();
+        let part1: u64 = EXAMPLES.iter().map(|e| e.1).sum();
+        let part2: u64 = EXAMPLES.iter().map(|e| e.2).sum();
+        assert_eq!(Day18.part1(&homework), Some(part1.to_string()));
+        assert_eq!(Day18.part2(&homework), Some(part2.to_string()));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let homework = Day18.parse("99999999999 * 99999999999").unwrap();
+        assert_eq!(Day18.part1(&homework), None);
+        assert_eq!(Day18.part2(&homework), None);
+
+        // each line fits, but not their total
+        let homework = Day18
+            .parse("9999999999 * 999999999\n18446744063709551616")
+            .unwrap();
+        assert_eq!(
+            sum(&homework[1..], Precedence::EQUAL),
+            Some(18446744063709551616)
+        );
+        assert_eq!(Day18.part1(&homework), None);
+    }
+
+    #[test]
+    fn test_sum_malformed() {
+        // tokens that never went through Day18::parse
+        let homework = vec![tokenize("1 + 2").unwrap(), tokenize("3 *").unwrap()];
+        assert_eq!(sum(&homework, Precedence::EQUAL), None);
+        assert_eq!(sum(&homework[..1], Precedence::EQUAL), Some(3));
+    }
+}
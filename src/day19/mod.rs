@@ -0,0 +1,544 @@
+use crate::error::{column, parse_number};
+use crate::record::{self, Record};
+use crate::{Error, Result, Solution};
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Range;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input<'a> = Puzzle<'a>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        Puzzle::try_from(text)
+    }
+
+    fn part1(&self, puzzle: &Self::Input<'_>) -> Option<String> {
+        let count = puzzle.grammar.matching(&puzzle.messages).count();
+        Some(count.to_string())
+    }
+
+    fn part2(&self, puzzle: &Self::Input<'_>) -> Option<String> {
+        let grammar = puzzle.grammar.with_loops()?;
+        Some(grammar.matching(&puzzle.messages).count().to_string())
+    }
+}
+
+pub type RuleId = usize;
+
+/// The rule every message has to match.
+pub const START: RuleId = 0;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rule {
+    /// e.g. `"a"`
+    Literal(String),
+    /// e.g. `2 3 | 3 2`: any one of the sequences of rules.
+    Alternatives(Vec<Vec<RuleId>>),
+}
+
+/// A set of numbered rules, which may refer to each other recursively.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grammar {
+    rules: HashMap<RuleId, Rule>,
+}
+
+/// A rule, each rule it refers to and the column each reference is at.
+type Definition = (RuleId, Rule, Vec<(usize, RuleId)>);
+
+/// e.g. `1: 2 3 | 3 2` or `4: "a"`
+fn parse_rule(s: &str) -> Result<Definition> {
+    let colon = s
+        .find(':')
+        .ok_or_else(|| Error::parse(1, "expected \"<id>: <rule>\""))?;
+    let id = parse_number(s[..colon].trim(), 1)?;
+
+    let body = &s[colon + 1..];
+    let start = column(s, colon + 1 + body.len() - body.trim_start().len());
+    let body = body.trim();
+
+    if let Some(quoted) = body.strip_prefix('"') {
+        return match quoted.strip_suffix('"') {
+            Some("") => Err(Error::parse(start, "empty literal")),
+            Some(literal) => Ok((id, Rule::Literal(literal.to_owned()), Vec::new())),
+            None => Err(Error::parse(start, "unterminated literal")),
+        };
+    }
+
+    let mut references = Vec::new();
+    let mut alternatives = Vec::new();
+    let mut column = start;
+    for alternative in body.split('|') {
+        let mut sequence = Vec::new();
+        let mut field_column = column;
+        for field in alternative.split(' ') {
+            if !field.is_empty() {
+                let rule = parse_number(field, field_column)?;
+                references.push((field_column, rule));
+                sequence.push(rule);
+            }
+            field_column += field.chars().count() + 1;
+        }
+        if sequence.is_empty() {
+            return Err(Error::parse(column, "empty alternative"));
+        }
+        alternatives.push(sequence);
+        column += alternative.chars().count() + 1;
+    }
+
+    Ok((id, Rule::Alternatives(alternatives), references))
+}
+
+impl Grammar {
+    /// Parses one rule per line, checking every rule referred to is defined.
+    pub fn from_record(record: &Record) -> Result<Self> {
+        let mut rules = HashMap::new();
+        let mut references = Vec::new();
+        for line in record.lines() {
+            let (id, rule, refs) = line.parse_with(parse_rule)?;
+            if rules.insert(id, rule).is_some() {
+                let message = format!("rule {} is defined twice", id);
+                return Err(line.locate(Error::parse(1, message)));
+            }
+            references.extend(refs.into_iter().map(|r| (line, r)));
+        }
+
+        if let Some((line, (column, id))) = references
+            .into_iter()
+            .find(|(_, (_, id))| !rules.contains_key(id))
+        {
+            let message = format!("rule {} is not defined", id);
+            return Err(line.locate(Error::parse(column, message)));
+        }
+        if !rules.contains_key(&START) {
+            return Err(Error::parse(1, "missing rule 0").offset(record.line() - 1, 0));
+        }
+
+        Ok(Self { rules })
+    }
+
+    pub fn rule(&self, id: RuleId) -> Option<&Rule> {
+        self.rules.get(&id)
+    }
+
+    /// Replaces rules 8 and 11 with the looping versions from part 2, or
+    /// `None` if the rules they loop over are not defined.
+    pub fn with_loops(&self) -> Option<Self> {
+        if !self.rules.contains_key(&42) || !self.rules.contains_key(&31) {
+            return None;
+        }
+        let mut grammar = self.clone();
+        grammar
+            .rules
+            .insert(8, Rule::Alternatives(vec![vec![42], vec![42, 8]]));
+        grammar
+            .rules
+            .insert(11, Rule::Alternatives(vec![vec![42, 31], vec![42, 11, 31]]));
+        Some(grammar)
+    }
+
+    /// Every position, in increasing order, at which a match of `rule`
+    /// starting at `start` in `message` can end.
+    pub fn match_ends(&self, rule: RuleId, message: &str, start: usize) -> Vec<usize> {
+        self.ends(rule, message, start, &mut Vec::new())
+    }
+
+    /// Whether the whole of `message` matches rule 0.
+    pub fn matches(&self, message: &str) -> bool {
+        self.match_ends(START, message, 0).contains(&message.len())
+    }
+
+    pub fn matching<'s, 'm>(
+        &'s self,
+        messages: &'s [&'m str],
+    ) -> impl Iterator<Item = &'m str> + 's {
+        messages.iter().copied().filter(move |m| self.matches(m))
+    }
+
+    /// How the whole of `message` matches rule 0, if it does.
+    pub fn derive<'m>(&self, message: &'m str) -> Option<Derivation<'m>> {
+        self.derive_span(START, message, 0..message.len(), &mut Vec::new())
+    }
+
+    /// Whether matching `id` at `start` again inside the matches in `active`
+    /// can't find anything new. Every rule consumes at least one character,
+    /// so in the shortest derivation each time a rule is nested in itself at
+    /// the same start (left recursion) it ends earlier, which can only happen
+    /// once per character left. Stopping there keeps left-recursive rules
+    /// from looping forever.
+    fn exhausted(active: &[(RuleId, usize)], id: RuleId, message: &str, start: usize) -> bool {
+        let nested = active.iter().filter(|&&a| a == (id, start)).count();
+        nested >= message.len().saturating_sub(start)
+    }
+
+    /// `active` holds the rules being matched and where they started.
+    fn ends(
+        &self,
+        id: RuleId,
+        message: &str,
+        start: usize,
+        active: &mut Vec<(RuleId, usize)>,
+    ) -> Vec<usize> {
+        if Self::exhausted(active, id, message, start) {
+            return Vec::new();
+        }
+
+        match &self.rules[&id] {
+            Rule::Literal(literal) => match message.get(start..) {
+                Some(rest) if rest.starts_with(literal.as_str()) => vec![start + literal.len()],
+                _ => Vec::new(),
+            },
+            Rule::Alternatives(alternatives) => {
+                active.push((id, start));
+                let mut ends = Vec::new();
+                for sequence in alternatives {
+                    let mut positions = vec![start];
+                    for &rule in sequence {
+                        let mut next = Vec::new();
+                        for position in positions {
+                            next.extend(self.ends(rule, message, position, active));
+                        }
+                        next.sort_unstable();
+                        next.dedup();
+                        positions = next;
+                    }
+                    ends.extend(positions);
+                }
+                active.pop();
+
+                ends.sort_unstable();
+                ends.dedup();
+                ends
+            }
+        }
+    }
+
+    fn derive_span<'m>(
+        &self,
+        id: RuleId,
+        message: &'m str,
+        span: Range<usize>,
+        active: &mut Vec<(RuleId, usize)>,
+    ) -> Option<Derivation<'m>> {
+        if Self::exhausted(active, id, message, span.start) {
+            return None;
+        }
+
+        let children = match &self.rules[&id] {
+            Rule::Literal(literal) => {
+                if message.get(span.clone()) != Some(literal.as_str()) {
+                    return None;
+                }
+                Vec::new()
+            }
+            Rule::Alternatives(alternatives) => {
+                active.push((id, span.start));
+                let children = alternatives
+                    .iter()
+                    .find_map(|sequence| self.derive_sequence(sequence, message, &span, active));
+                active.pop();
+                children?
+            }
+        };
+
+        Some(Derivation {
+            rule: id,
+            text: &message[span.clone()],
+            span,
+            children,
+        })
+    }
+
+    fn derive_sequence<'m>(
+        &self,
+        sequence: &[RuleId],
+        message: &'m str,
+        span: &Range<usize>,
+        active: &mut Vec<(RuleId, usize)>,
+    ) -> Option<Vec<Derivation<'m>>> {
+        let (&first, rest) = match sequence.split_first() {
+            Some(split) => split,
+            None => return (span.start == span.end).then(Vec::new),
+        };
+
+        for middle in self.ends(first, message, span.start, active) {
+            if middle > span.end {
+                break;
+            }
+            let tail = match self.derive_sequence(rest, message, &(middle..span.end), active) {
+                Some(tail) => tail,
+                None => continue,
+            };
+            if let Some(head) = self.derive_span(first, message, span.start..middle, active) {
+                let mut children = vec![head];
+                children.extend(tail);
+                return Some(children);
+            }
+        }
+        None
+    }
+}
+
+/// Which rule matched which part of a message, and how its parts matched.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Derivation<'m> {
+    pub rule: RuleId,
+    pub span: Range<usize>,
+    pub text: &'m str,
+    pub children: Vec<Derivation<'m>>,
+}
+
+impl<'m> Derivation<'m> {
+    fn write(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        writeln!(
+            f,
+            "{:indent$}{}: {:?}",
+            "",
+            self.rule,
+            self.text,
+            indent = 2 * depth
+        )?;
+        for child in &self.children {
+            child.write(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// One `<rule>: "<text>"` line per node, children indented under parents.
+impl<'m> fmt::Display for Derivation<'m> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Puzzle<'a> {
+    grammar: Grammar,
+    messages: Vec<&'a str>,
+}
+
+impl<'a> TryFrom<&'a str> for Puzzle<'a> {
+    type Error = Error;
+
+    /// The rules, then the messages after a blank line.
+    fn try_from(s: &'a str) -> Result<Self> {
+        let records = record::split(s);
+        let (rules, messages) = match records.as_slice() {
+            [rules, messages] => (rules, messages),
+            [] | [_] => return Err(Error::parse(1, "expected rules and messages")),
+            [_, _, extra, ..] => {
+                return Err(Error::parse(1, "unexpected section after messages")
+                    .offset(extra.line() - 1, 0))
+            }
+        };
+
+        Ok(Self {
+            grammar: Grammar::from_record(rules)?,
+            messages: messages.texts().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: \"a\"
+5: \"b\"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb";
+
+    static LOOPING: &str = "42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: \"a\"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: \"b\"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba";
+
+    fn error(text: &str) -> String {
+        Puzzle::try_from(text).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        let (id, rule, references) = parse_rule("1: 2 3 | 3 2").unwrap();
+        assert_eq!(id, 1);
+        assert_eq!(rule, Rule::Alternatives(vec![vec![2, 3], vec![3, 2]]));
+        assert_eq!(references, [(4, 2), (6, 3), (10, 3), (12, 2)]);
+
+        let (id, rule, _) = parse_rule("4: \"a\"").unwrap();
+        assert_eq!(id, 4);
+        assert_eq!(rule, Rule::Literal("a".to_owned()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            error("0: 1 x\n1: \"a\"\n\na"),
+            "line 1, column 6: invalid digit found in string in \"x\""
+        );
+        assert_eq!(
+            error("0: 1 | 2\n1: \"a\"\n\na"),
+            "line 1, column 8: rule 2 is not defined"
+        );
+        assert_eq!(
+            error("0: 1\n1: \"a\"\n1: \"b\"\n\na"),
+            "line 3, column 1: rule 1 is defined twice"
+        );
+        assert_eq!(
+            error("0: 1 |\n1: \"a\"\n\na"),
+            "line 1, column 7: empty alternative"
+        );
+        assert_eq!(
+            error("0: \"a\n\na"),
+            "line 1, column 4: unterminated literal"
+        );
+        assert_eq!(error("\n1: \"a\"\n\na"), "line 2, column 1: missing rule 0");
+        assert_eq!(
+            error("0: \"a\""),
+            "line 1, column 1: expected rules and messages"
+        );
+    }
+
+    #[test]
+    fn test_match_ends() {
+        let puzzle = Puzzle::try_from(EXAMPLE).unwrap();
+        let grammar = &puzzle.grammar;
+        assert_eq!(grammar.match_ends(2, "aab", 0), [2]);
+        assert_eq!(grammar.match_ends(1, "aaab", 0), [4]);
+        assert_eq!(grammar.match_ends(1, "ab", 0), Vec::<usize>::new());
+
+        let grammar = Puzzle::try_from(LOOPING)
+            .unwrap()
+            .grammar
+            .with_loops()
+            .unwrap();
+        // Rule 42 matches five characters at a time and rule 8 repeats it,
+        // so rule 8 can end after each repetition that matches.
+        assert_eq!(grammar.match_ends(42, "bbabbbbaabaabba", 0), [5]);
+        assert_eq!(grammar.match_ends(8, "bbabbbbaabaabba", 0), [5, 10]);
+    }
+
+    #[test]
+    fn test_part1() {
+        let puzzle = Puzzle::try_from(EXAMPLE).unwrap();
+        assert_eq!(
+            puzzle
+                .grammar
+                .matching(&puzzle.messages)
+                .collect::<Vec<_>>(),
+            ["ababbb", "abbbab"]
+        );
+        assert_eq!(Day19.part1(&puzzle), Some("2".to_owned()));
+        assert_eq!(Day19.part2(&puzzle), None);
+
+        let puzzle = Puzzle::try_from(LOOPING).unwrap();
+        assert_eq!(Day19.part1(&puzzle), Some("3".to_owned()));
+    }
+
+    #[test]
+    fn test_part2() {
+        let puzzle = Puzzle::try_from(LOOPING).unwrap();
+        assert_eq!(Day19.part2(&puzzle), Some("12".to_owned()));
+    }
+
+    #[test]
+    fn test_left_recursion() {
+        let puzzle = Puzzle::try_from("0: 0 1 | 1\n1: \"a\"\n\naaa\nab").unwrap();
+        assert_eq!(puzzle.grammar.match_ends(0, "aaa", 0), [1, 2, 3]);
+        assert_eq!(puzzle.grammar.match_ends(0, "aaa", 2), [3]);
+        assert_eq!(Day19.part1(&puzzle), Some("1".to_owned()));
+        assert_eq!(
+            puzzle.grammar.derive("aaa").unwrap().to_string(),
+            "0: \"aaa\"
+  0: \"aa\"
+    0: \"a\"
+      1: \"a\"
+    1: \"a\"
+  1: \"a\"
+"
+        );
+
+        // Indirect left recursion, and a rule that is only ever itself.
+        let puzzle = Puzzle::try_from("0: 2 | 3\n2: 0 1 | 1\n3: 3\n1: \"a\"\n\naaaa").unwrap();
+        assert_eq!(puzzle.grammar.match_ends(0, "aaaa", 0), [1, 2, 3, 4]);
+        assert!(puzzle.grammar.derive("aaaa").is_some());
+        assert!(puzzle.grammar.match_ends(3, "aaaa", 0).is_empty());
+    }
+
+    #[test]
+    fn test_derive() {
+        let puzzle = Puzzle::try_from(EXAMPLE).unwrap();
+        assert!(puzzle.grammar.derive("bababa").is_none());
+
+        let derivation = puzzle.grammar.derive("ababbb").unwrap();
+        assert_eq!(derivation.span, 0..6);
+        assert_eq!(
+            derivation.to_string(),
+            "0: \"ababbb\"
+  4: \"a\"
+  1: \"babb\"
+    3: \"ba\"
+      5: \"b\"
+      4: \"a\"
+    2: \"bb\"
+      5: \"b\"
+      5: \"b\"
+  5: \"b\"
+"
+        );
+    }
+}
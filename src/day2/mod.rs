@@ -0,0 +1,180 @@
+use crate::error::{column, parse_number};
+use crate::{Error, Result, Solution};
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = Vec<Entry<'a>>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        crate::parse_lines_with(text, Entry::try_from)
+    }
+
+    fn part1(&self, entries: &Self::Input<'_>) -> Option<String> {
+        Some(part1(entries).to_string())
+    }
+
+    fn part2(&self, entries: &Self::Input<'_>) -> Option<String> {
+        Some(part2(entries).to_string())
+    }
+}
+
+#[derive(Debug)]
+pub struct CorporatePolicy {
+    first: u32,
+    second: u32,
+    letter: char,
+}
+
+impl FromStr for CorporatePolicy {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let hyphen = text
+            .find('-')
+            .ok_or_else(|| Error::parse(1, "no hyphen in policy"))?;
+        let space = text
+            .find(' ')
+            .ok_or_else(|| Error::parse(1, "no space in policy"))?;
+        if space < hyphen {
+            return Err(Error::unexpected(column(text, space), ' ', "'-'"));
+        }
+        let first = parse_number(&text[0..hyphen], 1)?;
+        let second = parse_number(&text[hyphen + 1..space], column(text, hyphen + 1))?;
+        if first == 0 || second == 0 {
+            return Err(Error::parse(1, "policy positions start at 1"));
+        }
+        let mut letters = text[space + 1..].chars();
+        let letter = match (letters.next(), letters.next()) {
+            (Some(letter), None) => letter,
+            (None, _) => return Err(Error::parse(column(text, space + 1), "no letter in policy")),
+            (Some(_), Some(c)) => {
+                return Err(Error::unexpected(
+                    column(text, space + 1) + 1,
+                    c,
+                    "end of policy",
+                ))
+            }
+        };
+
+        Ok(CorporatePolicy {
+            first,
+            second,
+            letter,
+        })
+    }
+}
+
+impl CorporatePolicy {
+    fn is_valid_low_high(&self, password: &str) -> bool {
+        let mut count = 0;
+        for letter in password.chars() {
+            if letter == self.letter {
+                count += 1;
+            }
+        }
+
+        self.first <= count && count <= self.second
+    }
+
+    fn is_valid_positions(&self, password: &str) -> bool {
+        let at = |position: u32| password.chars().nth(position as usize - 1) == Some(self.letter);
+
+        at(self.first) ^ at(self.second)
+    }
+}
+
+/// One line of the password database: a policy and the password it applies to.
+#[derive(Debug)]
+pub struct Entry<'a> {
+    policy: CorporatePolicy,
+    password: &'a str,
+}
+
+impl<'a> TryFrom<&'a str> for Entry<'a> {
+    type Error = Error;
+
+    fn try_from(line: &'a str) -> Result<Self> {
+        let separator = line
+            .find(": ")
+            .ok_or_else(|| Error::parse(1, "missing password in line"))?;
+        let policy = line[..separator].parse()?;
+        let password = &line[separator + 2..];
+
+        Ok(Self { policy, password })
+    }
+}
+
+pub fn part1(entries: &[Entry]) -> usize {
+    entries
+        .iter()
+        .filter(|e| e.policy.is_valid_low_high(e.password))
+        .map(|_| 1)
+        .sum()
+}
+
+pub fn part2(entries: &[Entry]) -> usize {
+    entries
+        .iter()
+        .filter(|e| e.policy.is_valid_positions(e.password))
+        .map(|_| 1)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corporate_policy() {
+        let policy: CorporatePolicy = "1-3 a".parse().unwrap();
+        assert!(policy.is_valid_low_high("abcde"));
+        assert!(policy.is_valid_positions("abcde"));
+
+        let policy: CorporatePolicy = "1-3 b".parse().unwrap();
+        assert!(!policy.is_valid_low_high("cdefg"));
+        assert!(!policy.is_valid_positions("cdefg"));
+
+        let policy: CorporatePolicy = "2-9 c".parse().unwrap();
+        assert!(policy.is_valid_low_high("ccccccccc"));
+        assert!(!policy.is_valid_positions("ccccccccc"));
+    }
+
+    #[test]
+    fn test_corporate_policy_errors() {
+        assert!("1-3".parse::<CorporatePolicy>().is_err());
+        assert!("1 3-a".parse::<CorporatePolicy>().is_err());
+        assert!("0-3 a".parse::<CorporatePolicy>().is_err());
+        assert_eq!(
+            "1-x a".parse::<CorporatePolicy>().unwrap_err().to_string(),
+            "line 1, column 3: invalid digit found in string in \"x\""
+        );
+        assert!("1-3 ab".parse::<CorporatePolicy>().is_err());
+    }
+
+    #[test]
+    fn test_part1() {
+        let entries = Day2
+            .parse("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc")
+            .unwrap();
+
+        assert_eq!(part1(&entries), 2);
+    }
+
+    #[test]
+    fn test_part2() {
+        let entries = Day2.parse("1-3 a: abcde").unwrap();
+
+        assert_eq!(part2(&entries), 1);
+    }
+
+    #[test]
+    fn test_parse_error_line() {
+        let e = Day2.parse("1-3 a: abcde\n1-3 b cdefg").unwrap_err();
+
+        assert_eq!(e.to_string(), "line 2, column 1: missing password in line");
+    }
+}
@@ -0,0 +1,785 @@
+use crate::error::parse_number;
+use crate::grid::{Grid, Position};
+use crate::record::{self, Record};
+use crate::{Error, Result, Solution};
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input<'a> = Vec<Tile>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        parse_tiles(text)
+    }
+
+    fn part1(&self, tiles: &Self::Input<'_>) -> Option<String> {
+        let arrangement = assemble(tiles)?;
+        let product: u64 = arrangement.corners().iter().map(|&t| tiles[t].id).product();
+        Some(product.to_string())
+    }
+
+    fn part2(&self, tiles: &Self::Input<'_>) -> Option<String> {
+        let image = assemble(tiles)?.image(tiles);
+        let sighting = Sighting::search(&image, &Pattern::sea_monster());
+        Some(sighting.roughness().to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pixel {
+    Off,
+    On,
+    /// Part of a sea monster, when highlighting them.
+    Monster,
+}
+
+impl TryFrom<char> for Pixel {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            '.' => Ok(Self::Off),
+            '#' => Ok(Self::On),
+            c => Err(Error::unexpected(1, c, "'.' or '#'")),
+        }
+    }
+}
+
+impl fmt::Display for Pixel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Self::Off => '.',
+            Self::On => '#',
+            Self::Monster => 'O',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+impl Side {
+    pub const ALL: [Self; 4] = [Self::Top, Self::Right, Self::Bottom, Self::Left];
+}
+
+/// The pixels along one side of a tile as bits, with `On` as 1, read left to
+/// right for the top and bottom and top to bottom for the sides. Two tiles fit
+/// side by side exactly when the edges that touch are equal.
+pub type Edge = u64;
+
+/// Tiles are at most this wide, so their edges fit in an `Edge`.
+pub const MAX_TILE_SIZE: usize = 64;
+
+/// Tiles need at least one pixel inside their border.
+pub const MIN_TILE_SIZE: usize = 3;
+
+fn fingerprint<'a>(pixels: impl Iterator<Item = &'a Pixel>) -> Edge {
+    pixels.fold(0, |bits, &pixel| bits << 1 | Edge::from(pixel == Pixel::On))
+}
+
+/// One of the eight ways to turn and flip a square.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Orientation {
+    /// Mirrored left to right before turning.
+    pub flipped: bool,
+    /// Quarter turns clockwise.
+    pub turns: u8,
+}
+
+impl Orientation {
+    pub const ALL: [Self; 8] = [
+        Self::new(false, 0),
+        Self::new(false, 1),
+        Self::new(false, 2),
+        Self::new(false, 3),
+        Self::new(true, 0),
+        Self::new(true, 1),
+        Self::new(true, 2),
+        Self::new(true, 3),
+    ];
+
+    pub const fn new(flipped: bool, turns: u8) -> Self {
+        Self { flipped, turns }
+    }
+
+    pub fn apply<T: Clone>(self, grid: &Grid<T>) -> Grid<T> {
+        let mut grid = if self.flipped {
+            grid.flip_horizontal()
+        } else {
+            grid.clone()
+        };
+        for _ in 0..self.turns {
+            grid = grid.rotate_clockwise();
+        }
+        grid
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tile {
+    pub id: u64,
+    image: Grid<Pixel>,
+}
+
+impl Tile {
+    /// e.g. `Tile 2311:` followed by the rows of a square image.
+    pub fn from_record(record: &Record) -> Result<Self> {
+        let header = &record.lines()[0];
+        let id = header.parse_with(|s| {
+            let id = s
+                .strip_prefix("Tile ")
+                .and_then(|s| s.strip_suffix(':'))
+                .ok_or_else(|| Error::unexpected(1, s, "\"Tile <id>:\""))?;
+            parse_number(id, 6)
+        })?;
+
+        let rows: Vec<&str> = record.texts().skip(1).collect();
+        let image: Grid<Pixel> = rows
+            .join("\n")
+            .parse()
+            .map_err(|e: Error| e.offset(header.number, 0))?;
+        if image.rows() != image.cols() {
+            let message = format!(
+                "expected a square tile, found {}x{}",
+                image.rows(),
+                image.cols()
+            );
+            return Err(header.locate(Error::parse(1, message)));
+        }
+        if image.rows() > MAX_TILE_SIZE {
+            let message = format!("tiles can be at most {} pixels wide", MAX_TILE_SIZE);
+            return Err(header.locate(Error::parse(1, message)));
+        }
+        if image.rows() < MIN_TILE_SIZE {
+            let message = format!("tiles must be at least {} pixels wide", MIN_TILE_SIZE);
+            return Err(header.locate(Error::parse(1, message)));
+        }
+
+        Ok(Self { id, image })
+    }
+
+    pub fn image(&self) -> &Grid<Pixel> {
+        &self.image
+    }
+
+    pub fn size(&self) -> usize {
+        self.image.rows()
+    }
+
+    pub fn oriented(&self, orientation: Orientation) -> Self {
+        Self {
+            id: self.id,
+            image: orientation.apply(&self.image),
+        }
+    }
+
+    pub fn edge(&self, side: Side) -> Edge {
+        let last = self.size() - 1;
+        match side {
+            Side::Top => fingerprint(self.image.row(0).iter()),
+            Side::Right => fingerprint(self.image.column(last)),
+            Side::Bottom => fingerprint(self.image.row(last).iter()),
+            Side::Left => fingerprint(self.image.column(0)),
+        }
+    }
+
+    /// The edges in the order of `Side::ALL`.
+    pub fn edges(&self) -> [Edge; 4] {
+        Side::ALL.map(|side| self.edge(side))
+    }
+
+    /// The image without its border.
+    pub fn interior(&self) -> Grid<Pixel> {
+        let inner = self.size().saturating_sub(2);
+        let cells = (1..=inner)
+            .flat_map(|row| self.image.row(row)[1..=inner].iter().copied())
+            .collect();
+        Grid::new(inner, inner, cells)
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Tile {}:\n{}", self.id, self.image)
+    }
+}
+
+/// Tiles separated by blank lines, all the same size.
+pub fn parse_tiles(text: &str) -> Result<Vec<Tile>> {
+    let records = record::split(text);
+    let tiles = records
+        .iter()
+        .map(Tile::from_record)
+        .collect::<Result<Vec<_>>>()?;
+
+    let size = tiles
+        .first()
+        .ok_or_else(|| Error::parse(1, "no tiles"))?
+        .size();
+    if let Some((record, tile)) = records.iter().zip(&tiles).find(|(_, t)| t.size() != size) {
+        let message = format!(
+            "expected a {}x{} tile like the first, found {}x{}",
+            size,
+            size,
+            tile.size(),
+            tile.size()
+        );
+        return Err(Error::parse(1, message).offset(record.line() - 1, 0));
+    }
+
+    Ok(tiles)
+}
+
+/// Where each tile goes in the assembled image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Arrangement {
+    /// How many tiles wide and high the image is.
+    side: usize,
+    /// The index of the tile at each spot in row order, and how it is turned.
+    tiles: Vec<(usize, Orientation)>,
+}
+
+impl Arrangement {
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    pub fn tile_at(&self, (row, col): Position) -> (usize, Orientation) {
+        self.tiles[row * self.side + col]
+    }
+
+    /// The indices of the tiles in the top left, top right, bottom left and
+    /// bottom right corners.
+    pub fn corners(&self) -> [usize; 4] {
+        let last = self.side - 1;
+        [(0, 0), (0, last), (last, 0), (last, last)].map(|spot| self.tile_at(spot).0)
+    }
+
+    /// The arranged tiles with their borders removed, stitched together.
+    pub fn image(&self, tiles: &[Tile]) -> Grid<Pixel> {
+        let interiors: Vec<Grid<Pixel>> = self
+            .tiles
+            .iter()
+            .map(|&(t, orientation)| tiles[t].oriented(orientation).interior())
+            .collect();
+        let inner = interiors[0].rows();
+
+        let mut cells = Vec::with_capacity(interiors.len() * inner * inner);
+        for tile_row in interiors.chunks(self.side) {
+            for row in 0..inner {
+                for interior in tile_row {
+                    cells.extend_from_slice(interior.row(row));
+                }
+            }
+        }
+        let size = self.side * inner;
+        Grid::new(size, size, cells)
+    }
+}
+
+/// Arranges the tiles in a square so that every pair of neighbouring edges
+/// matches, or `None` if they can't be.
+pub fn assemble(tiles: &[Tile]) -> Option<Arrangement> {
+    let side = (0..=tiles.len()).find(|s| s * s >= tiles.len())?;
+    if side * side != tiles.len() || side == 0 {
+        return None;
+    }
+
+    let edges: Vec<Vec<[Edge; 4]>> = tiles
+        .iter()
+        .map(|tile| {
+            Orientation::ALL
+                .iter()
+                .map(|&o| tile.oriented(o).edges())
+                .collect()
+        })
+        .collect();
+    let mut by_edge: HashMap<(Side, Edge), Vec<(usize, usize)>> = HashMap::new();
+    for (tile, orientations) in edges.iter().enumerate() {
+        for (orientation, tile_edges) in orientations.iter().enumerate() {
+            for (&side, &edge) in Side::ALL.iter().zip(tile_edges) {
+                by_edge
+                    .entry((side, edge))
+                    .or_default()
+                    .push((tile, orientation));
+            }
+        }
+    }
+
+    let mut assembler = Assembler {
+        side,
+        edges: &edges,
+        by_edge,
+        used: vec![false; tiles.len()],
+        placed: Vec::with_capacity(tiles.len()),
+    };
+    if !assembler.place() {
+        return None;
+    }
+
+    Some(Arrangement {
+        side,
+        tiles: assembler
+            .placed
+            .into_iter()
+            .map(|(tile, orientation)| (tile, Orientation::ALL[orientation]))
+            .collect(),
+    })
+}
+
+/// Places tiles in row order, backtracking on dead ends. Tiles and
+/// orientations are indices into the tiles and `Orientation::ALL`.
+struct Assembler<'a> {
+    side: usize,
+    /// The edges of every tile in every orientation.
+    edges: &'a [Vec<[Edge; 4]>],
+    /// Every tile and orientation with a given edge on a given side.
+    by_edge: HashMap<(Side, Edge), Vec<(usize, usize)>>,
+    used: Vec<bool>,
+    placed: Vec<(usize, usize)>,
+}
+
+impl<'a> Assembler<'a> {
+    fn edge(&self, spot: usize, side: Side) -> Edge {
+        let (tile, orientation) = self.placed[spot];
+        self.edges[tile][orientation][side as usize]
+    }
+
+    /// Fills the next free spot and every spot after it.
+    fn place(&mut self) -> bool {
+        let spot = self.placed.len();
+        if spot == self.used.len() {
+            return true;
+        }
+
+        let left = (!spot.is_multiple_of(self.side)).then(|| self.edge(spot - 1, Side::Right));
+        let above = (spot >= self.side).then(|| self.edge(spot - self.side, Side::Bottom));
+        let candidates = match (left, above) {
+            (Some(edge), _) => self.matching(Side::Left, edge),
+            (None, Some(edge)) => self.matching(Side::Top, edge),
+            (None, None) => (0..self.used.len())
+                .flat_map(|tile| (0..Orientation::ALL.len()).map(move |o| (tile, o)))
+                .collect(),
+        };
+
+        for (tile, orientation) in candidates {
+            if self.used[tile] {
+                continue;
+            }
+            let top = self.edges[tile][orientation][Side::Top as usize];
+            if above.is_some_and(|edge| edge != top) {
+                continue;
+            }
+
+            self.used[tile] = true;
+            self.placed.push((tile, orientation));
+            if self.place() {
+                return true;
+            }
+            self.placed.pop();
+            self.used[tile] = false;
+        }
+        false
+    }
+
+    fn matching(&self, side: Side, edge: Edge) -> Vec<(usize, usize)> {
+        self.by_edge.get(&(side, edge)).cloned().unwrap_or_default()
+    }
+}
+
+/// The sea monster to look for, one line per row.
+pub const SEA_MONSTER: &str = "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   ";
+
+/// The `#`s of a picture, to look for in an image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    rows: usize,
+    cols: usize,
+    offsets: Vec<Position>,
+}
+
+impl Pattern {
+    pub fn new(picture: &str) -> Self {
+        let offsets: Vec<Position> = picture
+            .lines()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.char_indices()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(col, _)| (row, col))
+            })
+            .collect();
+        Self {
+            rows: offsets.iter().map(|&(row, _)| row + 1).max().unwrap_or(0),
+            cols: offsets.iter().map(|&(_, col)| col + 1).max().unwrap_or(0),
+            offsets,
+        }
+    }
+
+    pub fn sea_monster() -> Self {
+        Self::new(SEA_MONSTER)
+    }
+
+    /// The top left corner of every place the pattern appears in the image,
+    /// in reading order. Occurrences may overlap.
+    pub fn occurrences(&self, image: &Grid<Pixel>) -> Vec<Position> {
+        let rows = (image.rows() + 1).saturating_sub(self.rows);
+        let cols = (image.cols() + 1).saturating_sub(self.cols);
+        (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .filter(|&(row, col)| {
+                self.offsets
+                    .iter()
+                    .all(|&(r, c)| image[(row + r, col + c)] != Pixel::Off)
+            })
+            .collect()
+    }
+}
+
+/// The patterns found in an image, in the first orientation of the image that
+/// has any.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sighting {
+    pub orientation: Orientation,
+    /// The top left corner of each occurrence.
+    pub found: Vec<Position>,
+    /// The image in that orientation with the patterns' pixels marked as
+    /// `Monster`.
+    pub highlighted: Grid<Pixel>,
+}
+
+impl Sighting {
+    pub fn search(image: &Grid<Pixel>, pattern: &Pattern) -> Self {
+        let (orientation, oriented, found) = Orientation::ALL
+            .iter()
+            .map(|&o| {
+                let oriented = o.apply(image);
+                let found = pattern.occurrences(&oriented);
+                (o, oriented, found)
+            })
+            .find(|(_, _, found)| !found.is_empty())
+            .unwrap_or_else(|| (Orientation::ALL[0], image.clone(), Vec::new()));
+
+        let mut highlighted = oriented;
+        for &(row, col) in &found {
+            for &(r, c) in &pattern.offsets {
+                highlighted[(row + r, col + c)] = Pixel::Monster;
+            }
+        }
+
+        Self {
+            orientation,
+            found,
+            highlighted,
+        }
+    }
+
+    /// How many `On` pixels are not part of any pattern.
+    pub fn roughness(&self) -> usize {
+        self.highlighted
+            .cells()
+            .iter()
+            .filter(|&&p| p == Pixel::On)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lcg;
+
+    static TILE: &str = "Tile 7:\n#..\n.#.\n##.";
+
+    static EXAMPLE: &str = "\
+Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...";
+
+    /// A pseudo-random image cut into `n`x`n` tiles of `size`x`size` pixels,
+    /// each turned at random and shuffled, with a sea monster drawn in at
+    /// `monster`. Returns the tiles, the IDs of the corner tiles and the
+    /// image they assemble into, in its original orientation.
+    fn puzzle(n: usize, size: usize, monster: Position) -> (Vec<Tile>, [u64; 4], Grid<Pixel>) {
+        let mut lcg = Lcg::new(2020);
+        let mut random = move |n: usize| lcg.below(n);
+
+        // Neighbouring tiles share their border, so the whole picture is one
+        // pixel wider than the tiles laid side by side without overlap.
+        let stride = size - 1;
+        let full = n * stride + 1;
+        let cells = (0..full * full)
+            .map(|_| match random(4) {
+                0 => Pixel::On,
+                _ => Pixel::Off,
+            })
+            .collect();
+        let mut picture = Grid::new(full, full, cells);
+
+        let inner = size - 2;
+        let to_picture = |i: usize| i / inner * stride + 1 + i % inner;
+        for &(r, c) in &Pattern::sea_monster().offsets {
+            picture[(to_picture(monster.0 + r), to_picture(monster.1 + c))] = Pixel::On;
+        }
+
+        let kept: Vec<usize> = (0..full).filter(|i| i % stride != 0).collect();
+        let image_cells = kept
+            .iter()
+            .flat_map(|&r| kept.iter().map(move |&c| (r, c)))
+            .map(|p| picture[p])
+            .collect();
+        let image = Grid::new(kept.len(), kept.len(), image_cells);
+
+        let id = |row: usize, col: usize| 1000 + (row * n + col) as u64;
+        let mut tiles = Vec::new();
+        for row in 0..n {
+            for col in 0..n {
+                let cells = (0..size)
+                    .flat_map(|r| (0..size).map(move |c| (row * stride + r, col * stride + c)))
+                    .map(|p| picture[p])
+                    .collect();
+                let tile = Tile {
+                    id: id(row, col),
+                    image: Grid::new(size, size, cells),
+                };
+                tiles.push(tile.oriented(Orientation::ALL[random(8)]));
+            }
+        }
+        for i in (1..tiles.len()).rev() {
+            tiles.swap(i, random(i + 1));
+        }
+
+        let last = n - 1;
+        let corners = [id(0, 0), id(0, last), id(last, 0), id(last, last)];
+        (tiles, corners, image)
+    }
+
+    #[test]
+    fn test_parse() {
+        let tiles = parse_tiles(TILE).unwrap();
+        assert_eq!(tiles.len(), 1);
+        assert_eq!(tiles[0].id, 7);
+        assert_eq!(tiles[0].to_string(), TILE);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |text: &str| parse_tiles(text).unwrap_err().to_string();
+        assert_eq!(
+            error("Tile x:\n#."),
+            "line 1, column 6: invalid digit found in string in \"x\""
+        );
+        assert_eq!(
+            error("Tile 1:\n#.\n.x"),
+            "line 3, column 2: expected '.' or '#', found \"x\""
+        );
+        assert_eq!(
+            error("Tile 1:\n#..\n.#."),
+            "line 1, column 1: expected a square tile, found 2x3"
+        );
+        assert_eq!(
+            error("Tile 1:\n#..\n.#.\n..#\n\nTile 2:\n#...\n.#..\n..#.\n...#"),
+            "line 6, column 1: expected a 3x3 tile like the first, found 4x4"
+        );
+        assert_eq!(
+            error("Tile 1:\n#.\n.#"),
+            "line 1, column 1: tiles must be at least 3 pixels wide"
+        );
+        assert_eq!(error("\n"), "line 1, column 1: no tiles");
+    }
+
+    #[test]
+    fn test_edges() {
+        let tile = &parse_tiles(TILE).unwrap()[0];
+        assert_eq!(tile.edges(), [0b100, 0b000, 0b110, 0b101]);
+        assert_eq!(tile.interior().to_string(), "#");
+
+        // Turning clockwise moves the left edge, reversed, to the top.
+        let turned = tile.oriented(Orientation::new(false, 1));
+        assert_eq!(turned.image().to_string(), "#.#\n##.\n...");
+        assert_eq!(turned.edge(Side::Top), 0b101);
+
+        let orientations: Vec<String> = Orientation::ALL
+            .iter()
+            .map(|&o| tile.oriented(o).image().to_string())
+            .collect();
+        for (i, a) in orientations.iter().enumerate() {
+            assert!(!orientations[i + 1..].contains(a), "{}", a);
+        }
+    }
+
+    #[test]
+    fn test_assemble() {
+        let (tiles, corners, image) = puzzle(3, 10, (5, 2));
+        let arrangement = assemble(&tiles).unwrap();
+        assert_eq!(arrangement.side(), 3);
+
+        let mut found: Vec<u64> = arrangement.corners().iter().map(|&t| tiles[t].id).collect();
+        let mut expected = corners.to_vec();
+        found.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(found, expected);
+
+        let assembled = arrangement.image(&tiles);
+        assert!(Orientation::ALL
+            .iter()
+            .any(|o| o.apply(&image) == assembled));
+
+        assert!(assemble(&tiles[1..]).is_none());
+    }
+
+    #[test]
+    fn test_sea_monsters() {
+        let pattern = Pattern::sea_monster();
+        assert_eq!(
+            (pattern.rows, pattern.cols, pattern.offsets.len()),
+            (3, 20, 15)
+        );
+
+        let (_, _, image) = puzzle(3, 10, (5, 2));
+        let turned = Orientation::new(true, 3).apply(&image);
+        let sighting = Sighting::search(&turned, &pattern);
+        assert_eq!(sighting.found, [(5, 2)]);
+        // Flipping and turning three times is its own inverse.
+        assert_eq!(sighting.orientation, Orientation::new(true, 3));
+
+        let on = image.cells().iter().filter(|&&p| p == Pixel::On).count();
+        assert_eq!(sighting.roughness(), on - 15);
+        let rendered = sighting.highlighted.to_string();
+        assert_eq!(rendered.matches('O').count(), 15);
+        assert_eq!(rendered.lines().nth(6).unwrap().find('O'), Some(2));
+    }
+
+    #[test]
+    fn test_example() {
+        let tiles = Day20.parse(EXAMPLE).unwrap();
+        assert_eq!(Day20.part1(&tiles), Some("20899048083289".to_owned()));
+        assert_eq!(Day20.part2(&tiles), Some("273".to_owned()));
+    }
+
+    #[test]
+    fn test_solution() {
+        let (tiles, corners, image) = puzzle(3, 10, (5, 2));
+        let text: Vec<String> = tiles.iter().map(Tile::to_string).collect();
+        let tiles = Day20.parse(&text.join("\n\n")).unwrap();
+
+        let product: u64 = corners.iter().product();
+        assert_eq!(Day20.part1(&tiles), Some(product.to_string()));
+        let on = image.cells().iter().filter(|&&p| p == Pixel::On).count();
+        assert_eq!(Day20.part2(&tiles), Some((on - 15).to_string()));
+    }
+}
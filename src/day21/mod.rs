@@ -0,0 +1,168 @@
+use crate::constraint::{Candidates, Unsolvable};
+use crate::error::column;
+use crate::{Error, Result, Solution};
+
+use std::convert::TryFrom;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input<'a> = Vec<Food<'a>>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        crate::parse_lines_with(text, Food::try_from)
+    }
+
+    fn part1(&self, foods: &Self::Input<'_>) -> Option<String> {
+        Some(safe_ingredient_count(foods).to_string())
+    }
+
+    fn part2(&self, foods: &Self::Input<'_>) -> Option<String> {
+        canonical_dangerous_ingredients(foods).ok()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Food<'a> {
+    ingredients: Vec<&'a str>,
+    allergens: Vec<&'a str>,
+}
+
+impl<'a> TryFrom<&'a str> for Food<'a> {
+    type Error = Error;
+
+    /// e.g. `mxmxvkd kfcds sqjhc nhms (contains dairy, fish)`, where the
+    /// allergens are optional.
+    fn try_from(s: &'a str) -> Result<Self> {
+        let (ingredients, allergens) = match s.find('(') {
+            Some(open) => {
+                let list = s[open + 1..]
+                    .strip_prefix("contains ")
+                    .ok_or_else(|| Error::parse(column(s, open + 1), "expected \"contains \""))?;
+                let list = list
+                    .trim_end()
+                    .strip_suffix(')')
+                    .ok_or_else(|| Error::parse(column(s, s.trim_end().len()), "expected ')'"))?;
+                let mut allergens = Vec::new();
+                let mut start = open + "(contains ".len();
+                for allergen in list.split(", ") {
+                    if allergen.trim().is_empty() {
+                        return Err(Error::parse(column(s, start), "empty allergen"));
+                    }
+                    allergens.push(allergen);
+                    start += allergen.len() + ", ".len();
+                }
+                (&s[..open], allergens)
+            }
+            None => (s, Vec::new()),
+        };
+
+        let ingredients: Vec<&str> = ingredients.split_whitespace().collect();
+        if ingredients.is_empty() {
+            return Err(Error::parse(1, "no ingredients"));
+        }
+
+        Ok(Self {
+            ingredients,
+            allergens,
+        })
+    }
+}
+
+/// The ingredients each allergen could be in: every ingredient of every food
+/// listing it.
+pub fn allergen_candidates<'a>(foods: &[Food<'a>]) -> Candidates<&'a str, &'a str> {
+    let mut candidates = Candidates::new();
+    for food in foods {
+        for &allergen in &food.allergens {
+            candidates.restrict(allergen, food.ingredients.iter().copied());
+        }
+    }
+    candidates
+}
+
+/// How many times ingredients that can't contain any allergen appear.
+pub fn safe_ingredient_count(foods: &[Food]) -> usize {
+    let candidates = allergen_candidates(foods);
+    foods
+        .iter()
+        .flat_map(|food| &food.ingredients)
+        .filter(|ingredient| !candidates.is_possible(ingredient))
+        .count()
+}
+
+/// The ingredient containing each allergen, sorted by allergen and separated
+/// by commas, or why they can't be worked out.
+pub fn canonical_dangerous_ingredients<'a>(
+    foods: &[Food<'a>],
+) -> std::result::Result<String, Unsolvable<&'a str, &'a str>> {
+    let dangerous = allergen_candidates(foods).solve()?;
+    Ok(dangerous.into_values().collect::<Vec<_>>().join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+
+    #[test]
+    fn test_parse() {
+        let food = Food::try_from("a b (contains c, d)").unwrap();
+        assert_eq!(food.ingredients, ["a", "b"]);
+        assert_eq!(food.allergens, ["c", "d"]);
+        assert!(Food::try_from("a b").unwrap().allergens.is_empty());
+
+        let error = |text: &str| Day21.parse(text).unwrap_err().to_string();
+        assert_eq!(
+            error("a\na b (dairy)"),
+            "line 2, column 6: expected \"contains \""
+        );
+        assert_eq!(
+            error("a b (contains dairy"),
+            "line 1, column 20: expected ')'"
+        );
+        assert_eq!(
+            error("(contains dairy)"),
+            "line 1, column 1: no ingredients"
+        );
+        assert_eq!(error("a (contains )"), "line 1, column 13: empty allergen");
+        assert_eq!(
+            error("a (contains dairy, , fish)"),
+            "line 1, column 20: empty allergen"
+        );
+        assert_eq!(
+            error("crème (contains é, , fish)"),
+            "line 1, column 20: empty allergen"
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        let foods = Day21.parse(EXAMPLE).unwrap();
+        assert_eq!(Day21.part1(&foods), Some("5".to_owned()));
+    }
+
+    #[test]
+    fn test_part2() {
+        let foods = Day21.parse(EXAMPLE).unwrap();
+        assert_eq!(Day21.part2(&foods), Some("mxmxvkd,sqjhc,fvjkl".to_owned()));
+
+        let foods = Day21.parse("a b (contains x, y)").unwrap();
+        let error = canonical_dangerous_ingredients(&foods).unwrap_err();
+        assert!(matches!(error, Unsolvable::UnderConstrained(_)));
+        assert_eq!(
+            error.to_string(),
+            "more than one candidate left for x (a, b); y (a, b)"
+        );
+        let foods = Day21.parse("a (contains x)\na (contains y)").unwrap();
+        assert_eq!(
+            canonical_dangerous_ingredients(&foods),
+            Err(Unsolvable::Contradiction("y"))
+        );
+        assert_eq!(Day21.part2(&foods), None);
+    }
+}
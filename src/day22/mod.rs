@@ -0,0 +1,449 @@
+use crate::error::parse_number;
+use crate::record::{self, Record};
+use crate::{Error, Result, Solution};
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input<'a> = [Deck; 2];
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        parse_decks(text)
+    }
+
+    fn part1(&self, decks: &Self::Input<'_>) -> Option<String> {
+        Game::new(false)
+            .play(decks)
+            .map(|outcome| outcome.score().to_string())
+    }
+
+    fn part2(&self, decks: &Self::Input<'_>) -> Option<String> {
+        Game::new(true)
+            .play(decks)
+            .map(|outcome| outcome.score().to_string())
+    }
+}
+
+pub type Card = u32;
+
+/// Cards from the top of the deck to the bottom.
+pub type Deck = VecDeque<Card>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Player {
+    One,
+    Two,
+}
+
+impl Player {
+    fn index(self) -> usize {
+        match self {
+            Self::One => 0,
+            Self::Two => 1,
+        }
+    }
+}
+
+impl fmt::Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.index() + 1)
+    }
+}
+
+/// e.g. `Player 1:` followed by one card per line.
+fn parse_deck(record: &Record, player: Player) -> Result<Deck> {
+    let header = &record.lines()[0];
+    let expected = format!("Player {}:", player);
+    if header.text != expected {
+        return Err(header.locate(Error::unexpected(1, header.text, expected)));
+    }
+    record.lines()[1..]
+        .iter()
+        .map(|line| line.parse_with(|s| parse_number(s, 1)))
+        .collect()
+}
+
+/// Each player's deck, separated by a blank line.
+pub fn parse_decks(text: &str) -> Result<[Deck; 2]> {
+    match record::split(text).as_slice() {
+        [one, two] => Ok([parse_deck(one, Player::One)?, parse_deck(two, Player::Two)?]),
+        [] | [_] => Err(Error::parse(1, "expected two decks")),
+        [_, _, extra, ..] => {
+            Err(Error::parse(1, "unexpected section after decks").offset(extra.line() - 1, 0))
+        }
+    }
+}
+
+/// What happened during a game, as the puzzle describes it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    Start {
+        game: usize,
+    },
+    Round {
+        game: usize,
+        round: usize,
+        /// Both decks before drawing.
+        decks: [Vec<Card>; 2],
+    },
+    /// A sub-game started from a round of `game`.
+    SubGame {
+        game: usize,
+    },
+    /// A sub-game whose winner was already known from an identical one.
+    Remembered {
+        winner: Player,
+    },
+    /// Play returned to `game` after a sub-game.
+    Resume {
+        game: usize,
+    },
+    RoundWon {
+        game: usize,
+        round: usize,
+        winner: Player,
+    },
+    /// The decks repeated an earlier round of the same game.
+    Repeated {
+        game: usize,
+    },
+    /// The decks repeated an earlier round of plain Combat, so it would go
+    /// on forever.
+    Endless {
+        game: usize,
+    },
+    GameWon {
+        game: usize,
+        winner: Player,
+    },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cards = |deck: &[Card]| {
+            let cards: Vec<String> = deck.iter().map(Card::to_string).collect();
+            cards.join(", ")
+        };
+
+        match self {
+            Self::Start { game } => write!(f, "=== Game {} ===", game),
+            Self::Round { game, round, decks } => write!(
+                f,
+                "-- Round {} (Game {}) --\nPlayer 1's deck: {}\nPlayer 2's deck: {}\n\
+                 Player 1 plays: {}\nPlayer 2 plays: {}",
+                round,
+                game,
+                cards(&decks[0]),
+                cards(&decks[1]),
+                decks[0][0],
+                decks[1][0]
+            ),
+            Self::SubGame { .. } => write!(f, "Playing a sub-game to determine the winner..."),
+            Self::Remembered { winner } => {
+                write!(
+                    f,
+                    "The winner of the sub-game is already known: player {}.",
+                    winner
+                )
+            }
+            Self::Resume { game } => write!(f, "...anyway, back to game {}.", game),
+            Self::RoundWon {
+                game,
+                round,
+                winner,
+            } => write!(
+                f,
+                "Player {} wins round {} of game {}!",
+                winner, round, game
+            ),
+            Self::Repeated { game } => write!(
+                f,
+                "These decks were already seen in game {}, so player 1 wins.",
+                game
+            ),
+            Self::Endless { game } => write!(
+                f,
+                "These decks were already seen in game {}, so nobody ever wins.",
+                game
+            ),
+            Self::GameWon { game, winner } => {
+                write!(f, "The winner of game {} is player {}!", game, winner)
+            }
+        }
+    }
+}
+
+/// How a game ended.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    pub winner: Player,
+    pub decks: [Deck; 2],
+}
+
+impl Outcome {
+    /// Each card in the winner's deck times its position from the bottom,
+    /// counting from 1.
+    pub fn score(&self) -> u64 {
+        self.decks[self.winner.index()]
+            .iter()
+            .rev()
+            .zip(1..)
+            .map(|(&card, position)| u64::from(card) * position)
+            .sum()
+    }
+}
+
+/// Plays Combat, or Recursive Combat, remembering the winner of every
+/// sub-game so identical sub-games are only played once.
+#[derive(Debug, Default)]
+pub struct Game {
+    recursive: bool,
+    winners: HashMap<[Vec<Card>; 2], Player>,
+    log: Option<Vec<Event>>,
+    games: usize,
+}
+
+impl Game {
+    pub fn new(recursive: bool) -> Self {
+        Self {
+            recursive,
+            ..Self::default()
+        }
+    }
+
+    /// Records every event, which makes playing much slower.
+    pub fn with_log(mut self) -> Self {
+        self.log = Some(Vec::new());
+        self
+    }
+
+    /// The events so far, if `with_log` was used.
+    pub fn log(&self) -> Option<&[Event]> {
+        self.log.as_deref()
+    }
+
+    /// Plays a fresh game, or returns `None` if plain Combat would never
+    /// end because the decks repeat.
+    pub fn play(&mut self, decks: &[Deck; 2]) -> Option<Outcome> {
+        self.games = 0;
+        if let Some(log) = &mut self.log {
+            log.clear();
+        }
+        self.game(decks.clone())
+    }
+
+    fn record(&mut self, event: impl FnOnce() -> Event) {
+        if let Some(log) = &mut self.log {
+            log.push(event());
+        }
+    }
+
+    fn game(&mut self, mut decks: [Deck; 2]) -> Option<Outcome> {
+        self.games += 1;
+        let game = self.games;
+        self.record(|| Event::Start { game });
+
+        let mut seen = HashSet::new();
+        let mut round = 0;
+        let winner = loop {
+            if decks[1].is_empty() {
+                break Player::One;
+            }
+            if decks[0].is_empty() {
+                break Player::Two;
+            }
+            if !seen.insert(decks.clone()) {
+                if !self.recursive {
+                    self.record(|| Event::Endless { game });
+                    return None;
+                }
+                self.record(|| Event::Repeated { game });
+                break Player::One;
+            }
+
+            round += 1;
+            self.record(|| Event::Round {
+                game,
+                round,
+                decks: [
+                    decks[0].iter().copied().collect(),
+                    decks[1].iter().copied().collect(),
+                ],
+            });
+            let played = [decks[0].pop_front().unwrap(), decks[1].pop_front().unwrap()];
+
+            let winner = if self.recursive
+                && decks[0].len() >= played[0] as usize
+                && decks[1].len() >= played[1] as usize
+            {
+                self.sub_game(game, &decks, played)?
+            } else if played[0] > played[1] {
+                Player::One
+            } else {
+                Player::Two
+            };
+            self.record(|| Event::RoundWon {
+                game,
+                round,
+                winner,
+            });
+
+            let (first, second) = match winner {
+                Player::One => (played[0], played[1]),
+                Player::Two => (played[1], played[0]),
+            };
+            decks[winner.index()].extend([first, second]);
+        };
+
+        self.record(|| Event::GameWon { game, winner });
+        Some(Outcome { winner, decks })
+    }
+
+    /// Plays a sub-game with as many cards from each deck as the card each
+    /// player just played.
+    fn sub_game(&mut self, game: usize, decks: &[Deck; 2], played: [Card; 2]) -> Option<Player> {
+        let sub_decks: [Vec<Card>; 2] =
+            [0, 1].map(|i| decks[i].iter().take(played[i] as usize).copied().collect());
+
+        self.record(|| Event::SubGame { game });
+        if let Some(&winner) = self.winners.get(&sub_decks) {
+            self.record(|| Event::Remembered { winner });
+            return Some(winner);
+        }
+
+        let winner = self.game(sub_decks.clone().map(Deck::from))?.winner;
+        self.winners.insert(sub_decks, winner);
+        self.record(|| Event::Resume { game });
+        Some(winner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10";
+
+    fn replay(game: &Game) -> Vec<String> {
+        game.log()
+            .unwrap()
+            .iter()
+            .flat_map(|e| e.to_string().lines().map(str::to_owned).collect::<Vec<_>>())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let decks = parse_decks(EXAMPLE).unwrap();
+        assert_eq!(decks[0], [9, 2, 6, 3, 1]);
+        assert_eq!(decks[1], [5, 8, 4, 7, 10]);
+
+        let error = |text: &str| parse_decks(text).unwrap_err().to_string();
+        assert_eq!(
+            error("Player 1:\n1\n\nPlayer 3:\n2"),
+            "line 4, column 1: expected Player 2:, found \"Player 3:\""
+        );
+        assert_eq!(
+            error("Player 1:\n1\nx\n\nPlayer 2:\n2"),
+            "line 3, column 1: invalid digit found in string in \"x\""
+        );
+        assert_eq!(
+            error("Player 1:\n1"),
+            "line 1, column 1: expected two decks"
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        let decks = parse_decks(EXAMPLE).unwrap();
+        let mut game = Game::new(false).with_log();
+        let outcome = game.play(&decks).unwrap();
+        assert_eq!(outcome.winner, Player::Two);
+        assert_eq!(outcome.decks[1], [3, 2, 10, 6, 8, 5, 9, 4, 7, 1]);
+        assert_eq!(outcome.score(), 306);
+
+        let replay = replay(&game);
+        assert_eq!(
+            replay[..7],
+            [
+                "=== Game 1 ===",
+                "-- Round 1 (Game 1) --",
+                "Player 1's deck: 9, 2, 6, 3, 1",
+                "Player 2's deck: 5, 8, 4, 7, 10",
+                "Player 1 plays: 9",
+                "Player 2 plays: 5",
+                "Player 1 wins round 1 of game 1!",
+            ]
+        );
+        assert!(replay.contains(&"Player 2 wins round 29 of game 1!".to_owned()));
+        assert_eq!(replay.last().unwrap(), "The winner of game 1 is player 2!");
+    }
+
+    #[test]
+    fn test_part2() {
+        let decks = parse_decks(EXAMPLE).unwrap();
+        let mut game = Game::new(true).with_log();
+        let outcome = game.play(&decks).unwrap();
+        assert_eq!(outcome.winner, Player::Two);
+        assert_eq!(outcome.decks[1], [7, 5, 6, 2, 4, 1, 10, 8, 9, 3]);
+        assert_eq!(outcome.score(), 291);
+
+        let replay = replay(&game);
+        let sub_game = replay
+            .iter()
+            .position(|l| l == "Playing a sub-game to determine the winner...")
+            .unwrap();
+        assert_eq!(replay[sub_game - 2], "Player 1 plays: 4");
+        assert_eq!(replay[sub_game - 1], "Player 2 plays: 3");
+        assert_eq!(replay[sub_game + 1], "=== Game 2 ===");
+        assert_eq!(replay.last().unwrap(), "The winner of game 1 is player 2!");
+        assert_eq!(Day22.part2(&decks), Some("291".to_owned()));
+    }
+
+    #[test]
+    fn test_repeated_decks() {
+        let decks = parse_decks("Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14").unwrap();
+        let mut game = Game::new(true).with_log();
+        assert_eq!(game.play(&decks).unwrap().winner, Player::One);
+        assert!(game.log().unwrap().contains(&Event::Repeated { game: 1 }));
+        assert_eq!(Day22.part2(&decks), Some("105".to_owned()));
+
+        let mut game = Game::new(false).with_log();
+        assert_eq!(game.play(&decks), None);
+        assert_eq!(
+            game.log().unwrap().last(),
+            Some(&Event::Endless { game: 1 })
+        );
+        assert_eq!(Day22.part1(&decks), None);
+    }
+
+    #[test]
+    fn test_play_again() {
+        let decks = parse_decks(EXAMPLE).unwrap();
+        let mut game = Game::new(true).with_log();
+        let first = game.play(&decks);
+        let events = game.log().unwrap().to_vec();
+        assert_eq!(game.play(&decks), first);
+        assert_eq!(game.log().unwrap()[0], Event::Start { game: 1 });
+        // sub-games the first play worked out are remembered this time
+        assert!(game.log().unwrap().len() < events.len());
+        assert!(game.log().unwrap().contains(&Event::Remembered {
+            winner: Player::Two
+        }));
+    }
+}
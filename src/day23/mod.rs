@@ -0,0 +1,216 @@
+use crate::{Error, Result, Solution};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = Vec<Cup>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        parse_cups(text.trim())
+    }
+
+    fn part1(&self, cups: &Self::Input<'_>) -> Option<String> {
+        let mut ring = Ring::new(cups, cups.len());
+        ring.play(100);
+        let labels: Vec<String> = ring.labels_from(1).skip(1).map(|c| c.to_string()).collect();
+        Some(labels.concat())
+    }
+
+    fn part2(&self, cups: &Self::Input<'_>) -> Option<String> {
+        let mut ring = Ring::new(cups, 1_000_000);
+        ring.play(10_000_000);
+        let product: u64 = ring.labels_from(1).skip(1).take(2).map(u64::from).product();
+        Some(product.to_string())
+    }
+}
+
+pub type Cup = u32;
+
+/// The current cup and the three picked up after it.
+pub const MIN_CUPS: usize = 4;
+
+/// One digit per cup, which must label the cups 1 to however many there are.
+pub fn parse_cups(s: &str) -> Result<Vec<Cup>> {
+    let mut cups = Vec::new();
+    for (i, c) in s.chars().enumerate() {
+        let cup = c
+            .to_digit(10)
+            .filter(|&d| d > 0)
+            .ok_or_else(|| Error::unexpected(i + 1, c, "a digit from 1 to 9"))?;
+        if cups.contains(&cup) {
+            return Err(Error::parse(i + 1, format!("cup {} appears twice", cup)));
+        }
+        cups.push(cup);
+    }
+
+    if cups.len() < MIN_CUPS {
+        let message = format!("expected at least {} cups", MIN_CUPS);
+        return Err(Error::parse(1, message));
+    }
+    if let Some(missing) = (1..=cups.len() as Cup).find(|c| !cups.contains(c)) {
+        let message = format!("cup {} is missing", missing);
+        return Err(Error::parse(1, message));
+    }
+    Ok(cups)
+}
+
+/// Cups in a circle, stored as the label of the cup clockwise of each label,
+/// so moving three cups is a handful of writes however many cups there are.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ring {
+    /// `next[label]` is clockwise of `label`. `next[0]` is unused.
+    next: Vec<Cup>,
+    current: Cup,
+}
+
+impl Ring {
+    /// The cups in the given order, followed by cups labelled one higher than
+    /// the last until there are `total` cups.
+    ///
+    /// # Panics
+    ///
+    /// If there are fewer than `MIN_CUPS` cups in total.
+    pub fn new(cups: &[Cup], total: usize) -> Self {
+        let total = total.max(cups.len());
+        assert!(total >= MIN_CUPS, "{} cups are too few to play", total);
+        let highest = cups.len() as Cup;
+        let order = cups
+            .iter()
+            .copied()
+            .chain(highest + 1..=total as Cup)
+            .collect::<Vec<_>>();
+
+        let mut next = vec![0; total + 1];
+        for pair in order.windows(2) {
+            next[pair[0] as usize] = pair[1];
+        }
+        next[order[total - 1] as usize] = order[0];
+
+        Self {
+            next,
+            current: order[0],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.next.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn current(&self) -> Cup {
+        self.current
+    }
+
+    fn next(&self, cup: Cup) -> Cup {
+        self.next[cup as usize]
+    }
+
+    /// Picks up the three cups after the current one, puts them down after
+    /// the highest lower label still on the table, wrapping around, and moves
+    /// to the next cup.
+    pub fn step(&mut self) {
+        let first = self.next(self.current);
+        let second = self.next(first);
+        let third = self.next(second);
+        let picked = [first, second, third];
+
+        let highest = self.len() as Cup;
+        let mut destination = self.current;
+        loop {
+            destination = if destination == 1 {
+                highest
+            } else {
+                destination - 1
+            };
+            if !picked.contains(&destination) {
+                break;
+            }
+        }
+
+        self.next[self.current as usize] = self.next(third);
+        self.next[third as usize] = self.next(destination);
+        self.next[destination as usize] = first;
+        self.current = self.next(self.current);
+    }
+
+    pub fn play(&mut self, moves: usize) {
+        for _ in 0..moves {
+            self.step();
+        }
+    }
+
+    /// Every label once, clockwise from `start`.
+    pub fn labels_from(&self, start: Cup) -> impl Iterator<Item = Cup> + '_ {
+        std::iter::successors(Some(start), move |&cup| Some(self.next(cup))).take(self.len())
+    }
+
+    /// The cups clockwise from `start`, with the current cup in parentheses
+    /// as the puzzle shows them.
+    pub fn render_from(&self, start: Cup) -> String {
+        let labels: Vec<String> = self
+            .labels_from(start)
+            .map(|cup| {
+                if cup == self.current {
+                    format!("({})", cup)
+                } else {
+                    cup.to_string()
+                }
+            })
+            .collect();
+        labels.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "389125467";
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |s: &str| parse_cups(s).unwrap_err().to_string();
+        assert_eq!(
+            error("3801"),
+            "line 1, column 3: expected a digit from 1 to 9, found \"0\""
+        );
+        assert_eq!(error("3123"), "line 1, column 4: cup 3 appears twice");
+        assert_eq!(error("1245"), "line 1, column 1: cup 3 is missing");
+        assert_eq!(error("312"), "line 1, column 1: expected at least 4 cups");
+    }
+
+    #[test]
+    fn test_step() {
+        let cups = parse_cups(EXAMPLE).unwrap();
+        let mut ring = Ring::new(&cups, cups.len());
+        assert_eq!(ring.render_from(3), "(3) 8 9 1 2 5 4 6 7");
+        ring.step();
+        assert_eq!(ring.render_from(3), "3 (2) 8 9 1 5 4 6 7");
+        ring.step();
+        assert_eq!(ring.render_from(3), "3 2 (5) 4 6 7 8 9 1");
+        ring.play(8);
+        assert_eq!(ring.render_from(5), "5 (8) 3 7 4 1 9 2 6");
+    }
+
+    #[test]
+    fn test_new_extends() {
+        let ring = Ring::new(&[3, 1, 2], 6);
+        assert_eq!(ring.labels_from(3).collect::<Vec<_>>(), [3, 1, 2, 4, 5, 6]);
+        assert_eq!(ring.labels_from(6).nth(1), Some(3));
+    }
+
+    #[test]
+    fn test_part1() {
+        let cups = Day23.parse(EXAMPLE).unwrap();
+        assert_eq!(Day23.part1(&cups), Some("67384529".to_owned()));
+    }
+
+    #[test]
+    fn test_part2() {
+        let cups = Day23.parse(EXAMPLE).unwrap();
+        assert_eq!(Day23.part2(&cups), Some("149245887792".to_owned()));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::hex::{self, Hex, HexDirection};
+use crate::{Result, Solution};
+
+use std::collections::{HashMap, HashSet};
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input<'a> = Vec<Vec<HexDirection>>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        crate::parse_lines_with(text, |line| hex::parse_path(line.trim_end()))
+    }
+
+    fn part1(&self, paths: &Self::Input<'_>) -> Option<String> {
+        Some(Floor::from_paths(paths).black_count().to_string())
+    }
+
+    fn part2(&self, paths: &Self::Input<'_>) -> Option<String> {
+        let mut floor = Floor::from_paths(paths);
+        floor.live(100);
+        Some(floor.black_count().to_string())
+    }
+}
+
+/// The tiles that are black side up; every other tile is white.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Floor {
+    black: HashSet<Hex>,
+}
+
+impl Floor {
+    /// Flips the tile at the end of each path, starting from the same
+    /// reference tile every time.
+    pub fn from_paths(paths: &[Vec<HexDirection>]) -> Self {
+        let mut floor = Self::default();
+        for path in paths {
+            floor.flip(Hex::ORIGIN.walk(path));
+        }
+        floor
+    }
+
+    pub fn flip(&mut self, tile: Hex) {
+        if !self.black.remove(&tile) {
+            self.black.insert(tile);
+        }
+    }
+
+    pub fn is_black(&self, tile: Hex) -> bool {
+        self.black.contains(&tile)
+    }
+
+    pub fn black_count(&self) -> usize {
+        self.black.len()
+    }
+
+    /// A black tile with no or more than two black neighbours turns white,
+    /// and a white tile with exactly two turns black.
+    pub fn day(&mut self) {
+        let mut black_neighbours: HashMap<Hex, usize> = HashMap::new();
+        for &tile in &self.black {
+            for neighbour in tile.neighbours() {
+                *black_neighbours.entry(neighbour).or_default() += 1;
+            }
+        }
+
+        self.black = black_neighbours
+            .into_iter()
+            .filter(|&(tile, count)| count == 2 || (count == 1 && self.black.contains(&tile)))
+            .map(|(tile, _)| tile)
+            .collect();
+    }
+
+    pub fn live(&mut self, days: usize) {
+        for _ in 0..days {
+            self.day();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "\
+sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew";
+
+    fn flipped(paths: &str) -> Floor {
+        Floor::from_paths(&Day24.parse(paths).unwrap())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Day24.parse("e\nwx").unwrap_err().to_string(),
+            "line 2, column 2: expected e, se, sw, w, nw or ne, found \"x\""
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        let floor = flipped("esew\nnwwswee\nesew\ne");
+        assert_eq!(floor.black_count(), 2);
+        assert!(floor.is_black(Hex::ORIGIN));
+        assert!(floor.is_black(Hex::ORIGIN.step(HexDirection::East)));
+
+        let paths = Day24.parse(EXAMPLE).unwrap();
+        assert_eq!(Day24.part1(&paths), Some("10".to_owned()));
+    }
+
+    #[test]
+    fn test_day() {
+        // Two black neighbours each keep one black neighbour, and the two
+        // white tiles touching both turn black.
+        let mut floor = flipped("\ne");
+        floor.day();
+        assert_eq!(floor.black_count(), 4);
+        assert!(floor.is_black(Hex::ORIGIN.step(HexDirection::NorthEast)));
+        assert!(floor.is_black(Hex::ORIGIN.step(HexDirection::SouthEast)));
+
+        // A lone black tile has no black neighbours.
+        let mut floor = flipped("e");
+        floor.day();
+        assert_eq!(floor.black_count(), 0);
+    }
+
+    #[test]
+    fn test_part2() {
+        // A triangle keeps two black neighbours per tile, and each white tile
+        // touching two of its sides turns black.
+        let paths = Day24.parse("\ne\nse").unwrap();
+        let mut floor = Floor::from_paths(&paths);
+        floor.day();
+        assert_eq!(floor.black_count(), 6);
+        assert!(floor.is_black(Hex::ORIGIN));
+        assert!(floor.is_black(Hex::new(1, 1)));
+        assert_eq!(Day24.part2(&paths), Some("0".to_owned()));
+
+        let paths = Day24.parse(EXAMPLE).unwrap();
+        let mut floor = Floor::from_paths(&paths);
+        floor.day();
+        assert_eq!(floor.black_count(), 15);
+        floor.live(9);
+        assert_eq!(floor.black_count(), 37);
+        assert_eq!(Day24.part2(&paths), Some("2208".to_owned()));
+    }
+}
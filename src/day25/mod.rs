@@ -0,0 +1,82 @@
+use crate::modular;
+use crate::{Error, Result, Solution};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input<'a> = [u128; 2];
+
+    /// The card's public key, then the door's.
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        match crate::parse_lines::<u128>(text.trim_end())?.as_slice() {
+            &[card, door] => Ok([card, door]),
+            keys => Err(Error::parse(
+                1,
+                format!("expected two public keys, found {}", keys.len()),
+            )),
+        }
+    }
+
+    fn part1(&self, &[card, door]: &Self::Input<'_>) -> Option<String> {
+        encryption_key(card, door).map(|key| key.to_string())
+    }
+
+    /// Day 25 only has one puzzle.
+    fn part2(&self, _keys: &Self::Input<'_>) -> Option<String> {
+        None
+    }
+}
+
+/// Every handshake transforms this subject number.
+pub const SUBJECT: u128 = 7;
+
+/// Transforming keeps values below this prime.
+pub const MODULUS: u128 = 20201227;
+
+/// Multiplies 1 by the subject number `loop_size` times.
+pub fn transform(subject: u128, loop_size: u128) -> u128 {
+    modular::pow_mod(subject, loop_size, MODULUS)
+}
+
+/// The smallest loop size that transforms `SUBJECT` into `public_key`.
+pub fn loop_size(public_key: u128) -> Option<u128> {
+    modular::discrete_log(SUBJECT, public_key, MODULUS)
+}
+
+/// The key both sides of the handshake arrive at: the door's public key
+/// transformed by the card's loop size, or the other way round.
+pub fn encryption_key(card: u128, door: u128) -> Option<u128> {
+    loop_size(card).map(|card_loop| transform(door, card_loop))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLE: &str = "5764801\n17807724\n";
+
+    #[test]
+    fn test_loop_size() {
+        assert_eq!(loop_size(5764801), Some(8));
+        assert_eq!(loop_size(17807724), Some(11));
+        assert_eq!(transform(SUBJECT, 8), 5764801);
+    }
+
+    #[test]
+    fn test_part1() {
+        let keys = Day25.parse(EXAMPLE).unwrap();
+        assert_eq!(encryption_key(keys[0], keys[1]), Some(14897079));
+        assert_eq!(encryption_key(keys[1], keys[0]), Some(14897079));
+        assert_eq!(Day25.part1(&keys), Some("14897079".to_owned()));
+        assert_eq!(Day25.part2(&keys), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Day25.parse("1\n2\n3").unwrap_err().to_string(),
+            "line 1, column 1: expected two public keys, found 3"
+        );
+        assert!(Day25.parse("1\nx").is_err());
+    }
+}
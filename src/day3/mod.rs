@@ -0,0 +1,171 @@
+use crate::grid::Grid;
+use crate::{Error, Result, Solution};
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = Map;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        text.parse()
+    }
+
+    fn part1(&self, map: &Self::Input<'_>) -> Option<String> {
+        let toboggan = Toboggan::new(3, 1);
+        Some(map.num_trees_traversed(&toboggan).to_string())
+    }
+
+    fn part2(&self, map: &Self::Input<'_>) -> Option<String> {
+        let toboggans = [
+            Toboggan::new(1, 1),
+            Toboggan::new(3, 1),
+            Toboggan::new(5, 1),
+            Toboggan::new(7, 1),
+            Toboggan::new(1, 2),
+        ];
+        Some(map.product_of_trees_from_toboggans(&toboggans).to_string())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GridSpace {
+    Open,
+    Tree,
+}
+
+impl TryFrom<char> for GridSpace {
+    type Error = Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            '.' => Ok(Self::Open),
+            '#' => Ok(Self::Tree),
+            c => Err(Error::unexpected(1, c, "'.' or '#'")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Map {
+    grid: Grid<GridSpace>,
+}
+
+impl Map {
+    pub fn product_of_trees_from_toboggans(&self, toboggans: &[Toboggan]) -> usize {
+        toboggans
+            .iter()
+            .map(|t| self.num_trees_traversed(t))
+            .product()
+    }
+
+    pub fn num_trees_traversed(&self, toboggan: &Toboggan) -> usize {
+        let mut num_trees = 0;
+        let (mut row, mut col) = (0, 0);
+
+        while row < self.grid.rows() {
+            if *self.get_space(row, col) == GridSpace::Tree {
+                num_trees += 1;
+            }
+            row += toboggan.down;
+            col += toboggan.right;
+        }
+
+        num_trees
+    }
+
+    pub fn get_space(&self, row: usize, col: usize) -> &GridSpace {
+        // Because of arboreal genetics and biome stability, the same pattern repeats to the right many times
+        self.grid.get_wrapped(row as isize, col as isize)
+    }
+}
+
+impl FromStr for Map {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self { grid: s.parse()? })
+    }
+}
+
+pub struct Toboggan {
+    right: usize,
+    down: usize,
+}
+
+impl Toboggan {
+    pub fn new(right: usize, down: usize) -> Self {
+        Self { right, down }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    #[test]
+    fn test_map_product_of_trees_from_toboggans() {
+        let map = INPUT.parse::<Map>().unwrap();
+
+        let toboggans = [
+            Toboggan::new(1, 1),
+            Toboggan::new(3, 1),
+            Toboggan::new(5, 1),
+            Toboggan::new(7, 1),
+            Toboggan::new(1, 2),
+        ];
+
+        assert_eq!(map.product_of_trees_from_toboggans(&toboggans), 336);
+    }
+
+    #[test]
+    fn test_map_num_trees_traversed() {
+        let map = INPUT.parse::<Map>().unwrap();
+
+        let toboggan = Toboggan::new(3, 1);
+
+        assert_eq!(map.num_trees_traversed(&toboggan), 7);
+    }
+
+    #[test]
+    fn test_map_from_str() {
+        let map: Map = ".#\n#.".parse().unwrap();
+
+        assert_eq!(map.grid.rows(), 2);
+        assert_eq!(map.grid.cols(), 2);
+    }
+
+    #[test]
+    fn test_gridspace_from_char() {
+        assert_eq!(GridSpace::try_from('.').unwrap(), GridSpace::Open);
+        assert_eq!(GridSpace::try_from('#').unwrap(), GridSpace::Tree);
+        assert!(GridSpace::try_from('x').is_err());
+    }
+
+    #[test]
+    fn test_map_from_str_errors() {
+        assert_eq!(
+            ".#\n#x".parse::<Map>().unwrap_err().to_string(),
+            "line 2, column 2: expected '.' or '#', found \"x\""
+        );
+        assert_eq!(
+            ".#\n#".parse::<Map>().unwrap_err().to_string(),
+            "line 2, column 1: expected 2 columns like the first row"
+        );
+        assert!("".parse::<Map>().is_err());
+    }
+}
@@ -1,24 +1,33 @@
-use input;
-use std::io::Result;
-fn main() -> Result<()> {
-    let input = input::load_file("src/day4/input.txt")?;
+use crate::record::{self, Record};
+use crate::{Error, Result, Solution};
 
-    println!("part1: {}", part1(&input));
-    println!("part2: {}", part2(&input));
+use std::convert::TryFrom;
 
-    Ok(())
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input<'a> = Vec<Passport<'a>>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        Passport::from_batch(text)
+    }
+
+    fn part1(&self, passports: &Self::Input<'_>) -> Option<String> {
+        Some(part1(passports).to_string())
+    }
+
+    fn part2(&self, passports: &Self::Input<'_>) -> Option<String> {
+        Some(part2(passports).to_string())
+    }
 }
 
-pub fn part1(input: &str) -> usize {
-    Passport::from_batch(input)
-        .into_iter()
-        .filter(|p| p.fields_present())
-        .count()
+pub fn part1(passports: &[Passport]) -> usize {
+    passports.iter().filter(|p| p.fields_present()).count()
 }
 
-pub fn part2(input: &str) -> usize {
-    Passport::from_batch(input)
-        .into_iter()
+pub fn part2(passports: &[Passport]) -> usize {
+    passports
+        .iter()
         .filter(|p| p.fields_present() & p.is_valid())
         .count()
 }
@@ -40,34 +49,35 @@ impl<'a> Field<'a> {
         match self {
             Self::BirthYear(year) => match year.chars().count() {
                 4 => match year.parse::<u32>() {
-                    Ok(year) => (1920 <= year) & (year <= 2002),
+                    Ok(year) => (1920..=2002).contains(&year),
                     Err(_) => false,
                 },
                 _ => false,
             },
             Self::IssueYear(year) => match year.chars().count() {
                 4 => match year.parse::<u32>() {
-                    Ok(year) => (2010 <= year) & (year <= 2020),
+                    Ok(year) => (2010..=2020).contains(&year),
                     Err(_) => false,
                 },
                 _ => false,
             },
             Self::ExpirationYear(year) => match year.chars().count() {
                 4 => match year.parse::<u32>() {
-                    Ok(year) => (2020 <= year) & (year <= 2030),
+                    Ok(year) => (2020..=2030).contains(&year),
                     Err(_) => false,
                 },
                 _ => false,
             },
             Self::Height(height) => {
-                let num_chars = height.chars().count();
-                let unit = &height[num_chars - 2..];
-                match (&height[0..num_chars - 2]).parse::<u8>() {
-                    Ok(height) => match unit {
-                        "in" => (59 <= height) & (height <= 76),
-                        "cm" => (150 <= height) & (height <= 193),
-                        _ => false,
-                    },
+                let (number, range) = if let Some(number) = height.strip_suffix("in") {
+                    (number, 59..=76)
+                } else if let Some(number) = height.strip_suffix("cm") {
+                    (number, 150..=193)
+                } else {
+                    return false;
+                };
+                match number.parse::<u8>() {
+                    Ok(height) => range.contains(&height),
                     Err(_) => false,
                 }
             }
@@ -87,11 +97,7 @@ impl<'a> Field<'a> {
                     None => return false,
                 }
 
-                chars.all(|c| match c {
-                    '0'..='9' => true,
-                    'a'..='f' => true,
-                    _ => false,
-                })
+                chars.all(|c| matches!(c, '0'..='9' | 'a'..='f'))
             }
             Self::EyeColor(color) => match *color {
                 // exactly one of: amb blu brn gry grn hzl oth
@@ -108,26 +114,32 @@ impl<'a> Field<'a> {
             Self::CountryID(_) => true,
         }
     }
+}
 
-    pub fn from(line: &'a str) -> Option<Self> {
+impl<'a> TryFrom<&'a str> for Field<'a> {
+    type Error = Error;
+
+    fn try_from(field: &'a str) -> Result<Self> {
         // separate key/value by ':'
-        match line.find(':') {
-            Some(i) => {
-                let key = &line[0..i];
-                let value = &line[i + 1..];
-                match key {
-                    "byr" => Some(Self::BirthYear(value)),
-                    "iyr" => Some(Self::IssueYear(value)),
-                    "eyr" => Some(Self::ExpirationYear(value)),
-                    "hgt" => Some(Self::Height(value)),
-                    "hcl" => Some(Self::HairColor(value)),
-                    "ecl" => Some(Self::EyeColor(value)),
-                    "pid" => Some(Self::PassportID(value)),
-                    "cid" => Some(Self::CountryID(value)),
-                    k => panic!("bad key: {}", k),
-                }
-            }
-            None => None,
+        let i = field
+            .find(':')
+            .ok_or_else(|| Error::unexpected(1, field, "a key:value field"))?;
+        let key = &field[0..i];
+        let value = &field[i + 1..];
+        match key {
+            "byr" => Ok(Self::BirthYear(value)),
+            "iyr" => Ok(Self::IssueYear(value)),
+            "eyr" => Ok(Self::ExpirationYear(value)),
+            "hgt" => Ok(Self::Height(value)),
+            "hcl" => Ok(Self::HairColor(value)),
+            "ecl" => Ok(Self::EyeColor(value)),
+            "pid" => Ok(Self::PassportID(value)),
+            "cid" => Ok(Self::CountryID(value)),
+            k => Err(Error::unexpected(
+                1,
+                k,
+                "one of byr, iyr, eyr, hgt, hcl, ecl, pid, cid",
+            )),
         }
     }
 }
@@ -159,42 +171,26 @@ impl<'a> Passport<'a> {
         mask | 1 << 7 == 255
     }
 
-    pub fn from_text(text: &'a str) -> Self {
+    /// Fields are separated by spaces or newlines.
+    pub fn from_record(record: &Record<'a>) -> Result<Self> {
         let mut p = vec![];
 
-        let spaces_newlines_at = text.char_indices().filter_map(|(i, c)| match c {
-            ' ' | '\n' => Some(i),
-            _ => None,
-        });
-
-        let mut prev = 0;
-
-        for i in spaces_newlines_at {
-            let field = &text[prev..i];
-
-            match Field::from(field) {
-                Some(f) => p.push(f),
-                None => {}
+        for line in record.lines() {
+            let mut col = 0;
+            for field in line.text.split(' ') {
+                if !field.is_empty() {
+                    p.push(Field::try_from(field).map_err(|e| line.locate(e.offset(0, col)))?);
+                }
+                col += field.chars().count() + 1;
             }
-
-            prev = i + 1;
-        }
-
-        let field = &text[prev..];
-
-        match Field::from(field) {
-            Some(f) => p.push(f),
-            None => {}
         }
 
-        Self(p)
+        Ok(Self(p))
     }
 
-    pub fn from_batch(batch: &'a str) -> Vec<Passport> {
-        batch
-            .split("\n\n")
-            .map(|part| Self::from_text(part))
-            .collect()
+    /// Passports are separated by blank lines.
+    pub fn from_batch(batch: &'a str) -> Result<Vec<Passport<'a>>> {
+        record::split(batch).iter().map(Self::from_record).collect()
     }
 }
 
@@ -220,6 +216,9 @@ mod tests {
         assert!(Field::Height("190cm").is_valid());
         assert!(!Field::Height("190in").is_valid());
         assert!(!Field::Height("190").is_valid());
+        assert!(!Field::Height("é12").is_valid());
+        assert!(!Field::Height("é").is_valid());
+        assert!(!Field::Height("60ïn").is_valid());
 
         assert!(Field::HairColor("#123abc").is_valid());
         assert!(!Field::HairColor("#123abz").is_valid());
@@ -248,7 +247,7 @@ mod tests {
         hcl:#cfa07d eyr:2025 pid:166559648
         iyr:2011 ecl:brn hgt:59in";
 
-        let num_valid = part1(batch);
+        let num_valid = part1(&Passport::from_batch(batch).unwrap());
 
         assert_eq!(num_valid, 2);
     }
@@ -269,7 +268,7 @@ mod tests {
         eyr:2038 hcl:74454a iyr:2023
         pid:3556412378 byr:2007";
 
-        let passports = Passport::from_batch(batch);
+        let passports = Passport::from_batch(batch).unwrap();
 
         for passport in passports {
             assert!(!passport.is_valid());
@@ -291,11 +290,50 @@ mod tests {
 
         iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
 
-        let passports = Passport::from_batch(batch);
+        let passports = Passport::from_batch(batch).unwrap();
 
         for passport in passports {
             // assert!(passport.is_valid());
             passport.is_valid();
         }
     }
+
+    #[test]
+    fn test_from_batch_errors() {
+        let batch = "byr:1937 iyr:2017\n\neyr:2020\nhcl:#fffffd xyz:1";
+        assert_eq!(
+            Passport::from_batch(batch).unwrap_err().to_string(),
+            "line 4, column 13: expected one of byr, iyr, eyr, hgt, hcl, ecl, pid, cid, found \"xyz\""
+        );
+
+        // columns count characters, so the two-byte é counts once
+        assert!(Passport::from_batch("hgt:é12 xyz:1")
+            .unwrap_err()
+            .to_string()
+            .starts_with("line 1, column 9: "));
+
+        assert!(Passport::from_batch("byr:1937 iyr2017").is_err());
+
+        let batch = "byr:1937\r\n\r\n\r\n    iyr:2017 abc:1\r\n\r\n";
+        assert!(Passport::from_batch(batch)
+            .unwrap_err()
+            .to_string()
+            .starts_with("line 4, column 14: "));
+    }
+
+    #[test]
+    fn test_from_batch_line_endings() {
+        let unix = "byr:1937 iyr:2017\ncid:147\n\neyr:2020\n";
+        let windows = "byr:1937 iyr:2017 \r\ncid:147\r\n\r\n\r\neyr:2020\r\n\r\n";
+
+        let lengths = |batch| -> Vec<usize> {
+            Passport::from_batch(batch)
+                .unwrap()
+                .iter()
+                .map(|p| p.0.len())
+                .collect()
+        };
+        assert_eq!(lengths(unix), [3, 1]);
+        assert_eq!(lengths(windows), [3, 1]);
+    }
 }
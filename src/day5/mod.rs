@@ -0,0 +1,137 @@
+use crate::{Error, Result, Solution};
+
+use std::str::FromStr;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input<'a> = Vec<Seat>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        crate::parse_lines(text)
+    }
+
+    fn part1(&self, seats: &Self::Input<'_>) -> Option<String> {
+        part1(seats).map(|id| id.to_string())
+    }
+
+    fn part2(&self, seats: &Self::Input<'_>) -> Option<String> {
+        part2(seats).map(|id| id.to_string())
+    }
+}
+
+pub fn part1(seats: &[Seat]) -> Option<usize> {
+    seats.iter().map(|s| s.seat_id()).max()
+}
+
+pub fn part2(seats: &[Seat]) -> Option<usize> {
+    let mut ids: Vec<usize> = seats.iter().map(|s| s.seat_id()).collect();
+    ids.sort();
+    let mut myid = *ids.first()?;
+    for id in &ids {
+        if *id == myid + 2 {
+            break;
+        } else {
+            myid = *id;
+        }
+    }
+
+    Some(myid + 1)
+}
+
+#[derive(Debug)]
+pub struct Seat {
+    partitions: [BinaryPartition; 10],
+}
+
+impl FromStr for Seat {
+    type Err = Error;
+
+    /// Seven F/B characters pick the row, then three L/R characters pick the column.
+    fn from_str(line: &str) -> Result<Self> {
+        let mut partitions = [BinaryPartition::Front; 10];
+        let mut chars = line.chars();
+
+        for (i, partition) in partitions.iter_mut().enumerate() {
+            let is_row = i < 7;
+            let expected = if is_row { "'F' or 'B'" } else { "'L' or 'R'" };
+            *partition = match (is_row, chars.next()) {
+                (true, Some('F')) => BinaryPartition::Front,
+                (true, Some('B')) => BinaryPartition::Back,
+                (false, Some('L')) => BinaryPartition::Left,
+                (false, Some('R')) => BinaryPartition::Right,
+                (_, Some(c)) => return Err(Error::unexpected(i + 1, c, expected)),
+                (_, None) => return Err(Error::unexpected(i + 1, "end of line", expected)),
+            };
+        }
+
+        if let Some(c) = chars.next() {
+            return Err(Error::unexpected(11, c, "end of line"));
+        }
+
+        Ok(Self { partitions })
+    }
+}
+
+impl Seat {
+    fn column(&self) -> usize {
+        let mut start = 0;
+        let mut end = 7;
+        for i in 7..10 {
+            match self.partitions[i] {
+                BinaryPartition::Left => end = (start + end) / 2,
+                BinaryPartition::Right => start = (start + end) / 2 + 1,
+                p => panic!("bad partition in last three: {:?}", p),
+            }
+        }
+        end
+    }
+
+    fn row(&self) -> usize {
+        let mut start = 0;
+        let mut end = 127;
+        for i in 0..7 {
+            match self.partitions[i] {
+                BinaryPartition::Front => end = (start + end) / 2,
+                BinaryPartition::Back => start = (start + end) / 2 + 1,
+                p => panic!("bad partition in first seven: {:?}", p),
+            }
+        }
+        start
+    }
+
+    fn seat_id(&self) -> usize {
+        self.row() * 8 + self.column()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum BinaryPartition {
+    Front,
+    Back,
+    Left,
+    Right,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let line: Seat = "FBFBBFFRLR".parse().unwrap();
+        assert_eq!(line.row(), 44);
+        assert_eq!(line.column(), 5);
+        assert_eq!(line.seat_id(), 357);
+    }
+
+    #[test]
+    fn test_seat_from_str_errors() {
+        assert_eq!(
+            "FBFBBFLRLR".parse::<Seat>().unwrap_err().to_string(),
+            "line 1, column 7: expected 'F' or 'B', found \"L\""
+        );
+        assert!("FBFBBFFRL".parse::<Seat>().is_err());
+        assert!("FBFBBFFRLRR".parse::<Seat>().is_err());
+    }
+}
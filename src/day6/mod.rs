@@ -1,33 +1,37 @@
-use input;
-use std::io::Result;
+use crate::{record, Result, Solution};
 
 use std::collections::HashMap;
 
-fn main() -> Result<()> {
-    let content = input::load_file("src/day6/input.txt")?;
+pub struct Day6;
 
-    println!("part1: {}", part1(&content));
+impl Solution for Day6 {
+    type Input<'a> = Plane<'a>;
 
-    println!("part2: {}", part2(&content));
-
-    Ok(())
-}
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        Ok(Plane::from(text))
+    }
 
-pub fn part1(text: &str) -> usize {
-    let plane = Plane::from(&text);
-    plane.sum_of_anyone_answers()
-}
+    fn part1(&self, plane: &Self::Input<'_>) -> Option<String> {
+        Some(plane.sum_of_anyone_answers().to_string())
+    }
 
-pub fn part2(text: &str) -> usize {
-    let plane = Plane::from(&text);
-    plane.sum_of_everyone_answers()
+    fn part2(&self, plane: &Self::Input<'_>) -> Option<String> {
+        Some(plane.sum_of_everyone_answers().to_string())
+    }
 }
 
 pub struct Plane<'a>(Vec<Group<'a>>);
 
 impl<'a> Plane<'a> {
     fn from(text: &'a str) -> Self {
-        Self(text.split("\n\n").map(|g| Group::from(g)).collect())
+        Self(
+            record::split(text)
+                .iter()
+                .map(|record| Group {
+                    answers: record.texts().collect(),
+                })
+                .collect(),
+        )
     }
 
     fn sum_of_anyone_answers(&self) -> usize {
@@ -44,12 +48,6 @@ pub struct Group<'a> {
 }
 
 impl<'a> Group<'a> {
-    pub fn from(text: &'a str) -> Self {
-        Self {
-            answers: text.lines().collect(),
-        }
-    }
-
     pub fn anyone_yes_answers(&self) -> usize {
         let mut answers = HashMap::new();
         for member_answer in &self.answers {
@@ -83,8 +81,8 @@ mod tests {
         let text = "abcx
 abcy
 abcz";
-        let group = Group::from(text);
-        assert_eq!(group.anyone_yes_answers(), 6);
+        let plane = Plane::from(text);
+        assert_eq!(plane.0[0].anyone_yes_answers(), 6);
     }
 
     #[test]
@@ -106,5 +104,9 @@ a
 b";
         let plane = Plane::from(text);
         assert_eq!(plane.sum_of_everyone_answers(), 6);
+
+        let text = text.replace('\n', "\r\n");
+        let plane = Plane::from(&text);
+        assert_eq!(plane.sum_of_everyone_answers(), 6);
     }
 }
@@ -1,22 +1,26 @@
-use input;
-use std::io::Result;
+use crate::error::{column, parse_number};
+use crate::{Error, Result, Solution};
+
+use std::convert::TryFrom;
 
 use std::collections::HashMap;
 
-fn main() -> Result<()> {
-    let content = input::load_file("src/day7/input.txt")?;
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input<'a> = Rules<'a>;
 
-    println!(
-        "part1: {}",
-        Rules::from(&content).num_bags_contain("shiny gold")
-    );
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        Rules::try_from(text)
+    }
 
-    println!(
-        "part2: {}",
-        Rules::from(&content).num_bags_hold("shiny gold")
-    );
+    fn part1(&self, rules: &Self::Input<'_>) -> Option<String> {
+        Some(rules.num_bags_contain("shiny gold").to_string())
+    }
 
-    Ok(())
+    fn part2(&self, rules: &Self::Input<'_>) -> Option<String> {
+        Some(rules.num_bags_hold("shiny gold").to_string())
+    }
 }
 
 #[derive(Debug)]
@@ -24,11 +28,15 @@ pub struct Rules<'a> {
     rules: HashMap<&'a str, Vec<(usize, &'a str)>>,
 }
 
-impl<'a> Rules<'a> {
-    pub fn from(text: &'a str) -> Self {
+impl<'a> TryFrom<&'a str> for Rules<'a> {
+    type Error = Error;
+
+    fn try_from(text: &'a str) -> Result<Self> {
         let mut rules = HashMap::new();
 
-        for rule in text.lines() {
+        for (row, rule) in text.lines().enumerate() {
+            let error = |column, message: &str| Error::parse(column, message).offset(row, 0);
+
             let spaces: Vec<usize> = rule
                 .char_indices()
                 .filter_map(|(i, c)| match c {
@@ -37,36 +45,47 @@ impl<'a> Rules<'a> {
                 })
                 .collect();
 
-            let key_bag = &rule[..*spaces.get(1).unwrap()];
+            let key_bag = match spaces.get(1) {
+                Some(&end) => &rule[..end],
+                None => return Err(error(1, "expected a bag color")),
+            };
             match spaces.len() {
                 6 => {
                     rules.insert(key_bag, vec![]);
                 }
-                n if (n - 7) % 4 == 0 => {
+                n if n >= 7 && (n - 7) % 4 == 0 => {
                     let num_bags = (n - 7) / 4 + 1;
 
                     let mut carry_abilities = vec![];
                     for i in 0..num_bags {
                         let j = i * 4 + 3;
-                        let start = *spaces.get(j).unwrap() + 1;
-                        let end = *spaces.get(j + 1).unwrap();
-                        let capacity = (&rule[start..end]).parse::<usize>().unwrap();
+                        let start = spaces[j] + 1;
+                        let end = spaces[j + 1];
+                        let capacity = parse_number(&rule[start..end], column(rule, start))
+                            .map_err(|e| e.offset(row, 0))?;
 
-                        let start = *spaces.get(j + 1).unwrap() + 1;
-                        let end = *spaces.get(j + 3).unwrap();
+                        let start = spaces[j + 1] + 1;
+                        let end = spaces[j + 3];
                         let value_bag = &rule[start..end];
 
                         carry_abilities.push((capacity, value_bag));
                     }
                     rules.insert(key_bag, carry_abilities);
                 }
-                _ => unreachable!(),
+                _ => {
+                    return Err(error(
+                        1,
+                        "expected \"<color> bags contain no other bags.\" or a list of \"<n> <color> bags\"",
+                    ))
+                }
             }
         }
 
-        Self { rules }
+        Ok(Self { rules })
     }
+}
 
+impl<'a> Rules<'a> {
     pub fn this_can_contain_that(&self, this: &str, that: &str) -> bool {
         match self.rules.get(this) {
             Some(bags) => bags
@@ -110,7 +129,7 @@ vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
 faded blue bags contain no other bags.
 dotted black bags contain no other bags.";
 
-        let rules = Rules::from(&input);
+        let rules = Rules::try_from(input).unwrap();
         assert_eq!(rules.num_bags_contain("shiny gold"), 4);
     }
 
@@ -126,7 +145,7 @@ vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
 faded blue bags contain no other bags.
 dotted black bags contain no other bags.";
 
-        let rules = Rules::from(&input);
+        let rules = Rules::try_from(input).unwrap();
         assert_eq!(rules.num_bags_hold("shiny gold"), 32);
 
         let input = "shiny gold bags contain 2 dark red bags.
@@ -137,7 +156,20 @@ dark green bags contain 2 dark blue bags.
 dark blue bags contain 2 dark violet bags.
 dark violet bags contain no other bags.";
 
-        let rules = Rules::from(&input);
+        let rules = Rules::try_from(input).unwrap();
         assert_eq!(rules.num_bags_hold("shiny gold"), 126);
     }
+
+    #[test]
+    fn test_rules_errors() {
+        let input = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain x bright white bags, 4 muted yellow bags.";
+        assert_eq!(
+            Rules::try_from(input).unwrap_err().to_string(),
+            "line 2, column 26: invalid digit found in string in \"x\""
+        );
+
+        assert!(Rules::try_from("light red bags contain").is_err());
+        assert!(Rules::try_from("light").is_err());
+    }
 }
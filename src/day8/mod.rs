@@ -0,0 +1,266 @@
+use crate::error::parse_number;
+use crate::{Error, Result, Solution};
+
+use std::str::FromStr;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input<'a> = HandheldGameConsole;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        text.parse()
+    }
+
+    fn part1(&self, console: &Self::Input<'_>) -> Option<String> {
+        let mut console = console.clone();
+        accumulator_before_inf_loop(&mut console).map(|acc| acc.to_string())
+    }
+
+    fn part2(&self, console: &Self::Input<'_>) -> Option<String> {
+        let mut console = console.clone();
+        accumulator_after_instruction_fix(&mut console).map(|acc| acc.to_string())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Operation {
+    Acc,
+    Jmp,
+    Nop,
+}
+
+pub type Argument = isize;
+
+#[derive(Clone)]
+pub struct Instruction(Operation, Argument);
+
+impl FromStr for Instruction {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let mut parts = line.split(' ');
+        // split always yields at least one part
+        let op = parts.next().unwrap_or_default();
+        let arg = parts
+            .next()
+            .ok_or_else(|| Error::parse(op.chars().count() + 1, "no argument in line"))?;
+        let arg = parse_number(arg, op.chars().count() + 2)?;
+        let op = match op {
+            "acc" => Operation::Acc,
+            "jmp" => Operation::Jmp,
+            "nop" => Operation::Nop,
+            op => return Err(Error::unexpected(1, op, "acc, jmp or nop")),
+        };
+        Ok(Self(op, arg))
+    }
+}
+
+#[derive(Clone)]
+pub struct HandheldGameConsole {
+    program_counter: usize,
+    accumulator: isize,
+    instructions: Vec<(bool, Instruction)>,
+}
+
+#[derive(Debug)]
+pub enum ConsoleError {
+    InfLoop,
+    /// The program ran the last instruction and moved on to the next one.
+    EndOfInstructions,
+    /// The program jumped anywhere else outside its instructions.
+    OutOfRange,
+}
+
+impl FromStr for HandheldGameConsole {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let instructions: Vec<Instruction> = crate::parse_lines(text)?;
+
+        Ok(Self {
+            program_counter: 0,
+            accumulator: 0,
+            instructions: instructions.into_iter().map(|i| (false, i)).collect(),
+        })
+    }
+}
+
+impl HandheldGameConsole {
+    fn reset(&mut self) {
+        self.program_counter = 0;
+        self.accumulator = 0;
+        self.instructions.iter_mut().for_each(|(e, _)| *e = false);
+    }
+
+    fn step(&mut self) -> std::result::Result<(), ConsoleError> {
+        let num_instructions = self.instructions.len();
+        let (has_executed, instruction) = match self.instructions.get_mut(self.program_counter) {
+            Some(instruction) => instruction,
+            None if self.program_counter == num_instructions => {
+                return Err(ConsoleError::EndOfInstructions)
+            }
+            None => return Err(ConsoleError::OutOfRange),
+        };
+
+        if *has_executed {
+            return Err(ConsoleError::InfLoop);
+        } else {
+            *has_executed = true;
+        }
+
+        match instruction.0 {
+            Operation::Acc => {
+                self.accumulator += instruction.1;
+                self.program_counter += 1;
+            }
+            Operation::Jmp => {
+                self.program_counter = self
+                    .program_counter
+                    .checked_add_signed(instruction.1)
+                    .ok_or(ConsoleError::OutOfRange)?
+            }
+            Operation::Nop => self.program_counter += 1,
+        }
+
+        Ok(())
+    }
+
+    fn flip_operation(&mut self, i: usize) {
+        let (_, instruction) = self.instructions.get_mut(i).expect("bad instruction index");
+
+        instruction.0 = match instruction.0 {
+            Operation::Acc => Operation::Acc,
+            Operation::Nop => Operation::Jmp,
+            Operation::Jmp => Operation::Nop,
+        };
+    }
+}
+
+/// The accumulator just before an instruction would run a second time, or
+/// `None` if the program stops instead.
+pub fn accumulator_before_inf_loop(console: &mut HandheldGameConsole) -> Option<isize> {
+    loop {
+        match console.step() {
+            Ok(()) => {}
+            Err(ConsoleError::InfLoop) => return Some(console.accumulator),
+            Err(_) => return None,
+        }
+    }
+}
+
+/// The accumulator once the program ends normally after swapping one `jmp`
+/// for a `nop` or the other way round, or `None` if no swap makes it end.
+pub fn accumulator_after_instruction_fix(console: &mut HandheldGameConsole) -> Option<isize> {
+    let num_instructions = console.instructions.len();
+    for i in 0..num_instructions {
+        if let Instruction(Operation::Acc, _) = console
+            .instructions
+            .get(i)
+            .expect("bad instruction index")
+            .1
+        {
+            continue;
+        }
+
+        console.reset();
+
+        console.flip_operation(i);
+
+        // test
+        loop {
+            if let Err(e) = console.step() {
+                match e {
+                    ConsoleError::EndOfInstructions => return Some(console.accumulator),
+                    ConsoleError::InfLoop | ConsoleError::OutOfRange => {
+                        break;
+                    }
+                }
+            }
+        }
+
+        // must not have been the right op.
+        // flip it back
+        console.flip_operation(i);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let text = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+        let mut console: HandheldGameConsole = text.parse().unwrap();
+
+        assert_eq!(accumulator_before_inf_loop(&mut console), Some(5));
+    }
+
+    #[test]
+    fn test_part2() {
+        let text = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+        let mut console: HandheldGameConsole = text.parse().unwrap();
+
+        assert_eq!(accumulator_after_instruction_fix(&mut console), Some(8));
+    }
+
+    #[test]
+    fn test_no_answer() {
+        // ends without looping
+        let console: HandheldGameConsole = "nop +0\nacc +1".parse().unwrap();
+        assert_eq!(Day8.part1(&console), None);
+
+        // jumps out of the program
+        let console: HandheldGameConsole = "jmp -5".parse().unwrap();
+        assert_eq!(Day8.part1(&console), None);
+        let console: HandheldGameConsole = "acc +1\njmp +5".parse().unwrap();
+        assert_eq!(Day8.part1(&console), None);
+
+        // no single swap stops the loop
+        let console: HandheldGameConsole = "acc +1\njmp +0\njmp -2".parse().unwrap();
+        assert_eq!(Day8.part1(&console), Some("1".to_owned()));
+        assert_eq!(Day8.part2(&console), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "nop +0\nadd +1"
+                .parse::<HandheldGameConsole>()
+                .err()
+                .unwrap()
+                .to_string(),
+            "line 2, column 1: expected acc, jmp or nop, found \"add\""
+        );
+        assert_eq!(
+            "nop +0\nacc"
+                .parse::<HandheldGameConsole>()
+                .err()
+                .unwrap()
+                .to_string(),
+            "line 2, column 4: no argument in line"
+        );
+        assert!("jmp +x".parse::<HandheldGameConsole>().is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{Result, Solution};
+
+use std::collections::VecDeque;
+
+pub struct Day9;
+
+const PREAMBLE_SIZE: usize = 25;
+
+impl Solution for Day9 {
+    type Input<'a> = Vec<usize>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {
+        crate::parse_lines(text)
+    }
+
+    fn part1(&self, data: &Self::Input<'_>) -> Option<String> {
+        first_number(data, PREAMBLE_SIZE).map(|num| num.to_string())
+    }
+
+    fn part2(&self, data: &Self::Input<'_>) -> Option<String> {
+        let num = first_number(data, PREAMBLE_SIZE)?;
+        sum_contiguous(data, num).map(|s| s.to_string())
+    }
+}
+
+/// The first number that is not the sum of two of the `preamble_size`
+/// numbers before it, if there is one.
+pub fn first_number(data: &[usize], preamble_size: usize) -> Option<usize> {
+    data.iter()
+        .enumerate()
+        .skip(preamble_size)
+        .find(|(i, c)| {
+            for j in (i - preamble_size)..(i - 1) {
+                for k in (j + 1)..*i {
+                    let a = data.get(j).unwrap();
+                    let b = data.get(k).unwrap();
+                    if *a + *b == **c {
+                        return false;
+                    }
+                }
+            }
+            true
+        })
+        .map(|(_, &c)| c)
+}
+
+/// The smallest plus the largest of the first run of at least two numbers
+/// that adds up to `num`, if there is one.
+pub fn sum_contiguous(data: &[usize], num: usize) -> Option<usize> {
+    let mut contiguous = VecDeque::new();
+    let mut sum = 0;
+    for &value in data {
+        contiguous.push_back(value);
+        sum += value;
+        while sum > num {
+            // start removing items
+            let value = contiguous.pop_front().unwrap();
+            sum -= value;
+        }
+        if sum == num && contiguous.len() >= 2 {
+            return Some(contiguous.iter().min()? + contiguous.iter().max()?);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let data = "35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+
+        let preamble_size = 5;
+
+        let data: Vec<usize> = crate::parse_lines(data).unwrap();
+
+        let num = first_number(&data, preamble_size).unwrap();
+        assert_eq!(num, 127);
+
+        let s = sum_contiguous(&data, num);
+        assert_eq!(s, Some(62));
+    }
+
+    #[test]
+    fn test_no_answer() {
+        let data = [1, 2, 3];
+        assert_eq!(first_number(&data, 2), None);
+        assert_eq!(Day9.part1(&data.to_vec()), None);
+        assert_eq!(Day9.part2(&data.to_vec()), None);
+
+        // 7 on its own is not a run of two
+        assert_eq!(sum_contiguous(&[1, 7, 10], 7), None);
+        assert_eq!(sum_contiguous(&[1, 7, 3, 4], 7), Some(7));
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+use std::path::PathBuf;
+
+/// Everything that can go wrong while loading or parsing a puzzle input.
+///
+/// Lines and columns are 1-based, and columns count characters rather than
+/// bytes. Parsers for a single line or fragment report
+/// positions relative to that fragment; callers that know where the fragment
+/// sits in the whole input move the position with `Error::offset`.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// There is no input file where one was expected.
+    MissingInput(PathBuf),
+    /// The input file exists but holds nothing but whitespace.
+    EmptyInput(PathBuf),
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    UnexpectedToken {
+        line: usize,
+        column: usize,
+        found: String,
+        expected: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn parse(column: usize, message: impl Into<String>) -> Self {
+        Self::Parse {
+            line: 1,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn unexpected(
+        column: usize,
+        found: impl fmt::Display,
+        expected: impl Into<String>,
+    ) -> Self {
+        Self::UnexpectedToken {
+            line: 1,
+            column,
+            found: found.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Moves the error's position down by `lines` lines. Columns only move on
+    /// the fragment's first line, since later lines start at column 1 anyway.
+    pub fn offset(self, lines: usize, columns: usize) -> Self {
+        match self {
+            Self::Parse {
+                line,
+                column,
+                message,
+            } => Self::Parse {
+                column: if line == 1 { column + columns } else { column },
+                line: line + lines,
+                message,
+            },
+            Self::UnexpectedToken {
+                line,
+                column,
+                found,
+                expected,
+            } => Self::UnexpectedToken {
+                column: if line == 1 { column + columns } else { column },
+                line: line + lines,
+                found,
+                expected,
+            },
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::MissingInput(path) => write!(f, "{}: no input file", path.display()),
+            Self::EmptyInput(path) => write!(f, "{}: input file is empty", path.display()),
+            Self::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            Self::UnexpectedToken {
+                line,
+                column,
+                found,
+                expected,
+            } => write!(
+                f,
+                "line {}, column {}: expected {}, found {:?}",
+                line, column, expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Lets grids of cells that can't fail to parse, like `char`, share the
+/// fallible parsing code.
+impl From<std::convert::Infallible> for Error {
+    fn from(e: std::convert::Infallible) -> Self {
+        match e {}
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Self::parse(1, e.to_string())
+    }
+}
+
+/// Parses `field` as a number, reporting failures at `column`.
+pub fn parse_number<T>(field: &str, column: usize) -> Result<T>
+where
+    T: std::str::FromStr<Err = ParseIntError>,
+{
+    field
+        .parse()
+        .map_err(|e| Error::parse(column, format!("{} in {:?}", e, field)))
+}
+
+/// The 1-based column of the character starting at byte `index` of `text`,
+/// for turning the byte offsets `str` methods return into error columns.
+pub fn column(text: &str, index: usize) -> usize {
+    text[..index].chars().count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset() {
+        let e = Error::parse(3, "bad").offset(4, 10);
+        assert_eq!(e.to_string(), "line 5, column 13: bad");
+
+        let e = Error::Parse {
+            line: 2,
+            column: 3,
+            message: "bad".to_owned(),
+        }
+        .offset(4, 10);
+        assert_eq!(e.to_string(), "line 6, column 3: bad");
+    }
+
+    #[test]
+    fn test_display() {
+        let e = Error::unexpected(2, 'x', "'.' or '#'");
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 2: expected '.' or '#', found \"x\""
+        );
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number::<u32>("12", 1).unwrap(), 12);
+        assert_eq!(
+            parse_number::<u32>("1x", 4).unwrap_err().to_string(),
+            "line 1, column 4: invalid digit found in string in \"1x\""
+        );
+    }
+
+    #[test]
+    fn test_column() {
+        assert_eq!(column("a\u{e9} b", 0), 1);
+        assert_eq!(column("a\u{e9} b", 3), 3);
+        assert_eq!(column("a\u{e9} b", 5), 5);
+    }
+}
@@ -0,0 +1,433 @@
+use crate::{Error, Result};
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// A `(row, column)` position in a grid, counting from the top left.
+pub type Position = (usize, usize);
+
+/// A `(rows, columns)` step between positions.
+pub type Direction = (isize, isize);
+
+/// Up, left, right and down.
+pub const ORTHOGONAL: [Direction; 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// The orthogonal and diagonal directions, in reading order.
+pub const DIRECTIONS: [Direction; 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangle of cells stored row by row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from cells in row order.
+    ///
+    /// # Panics
+    ///
+    /// If `cells` does not hold exactly `rows * cols` cells.
+    pub fn new(rows: usize, cols: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), rows * cols, "grid is not {}x{}", rows, cols);
+        Self { rows, cols, cells }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, (row, col): Position) -> Option<&T> {
+        if row < self.rows && col < self.cols {
+            self.cells.get(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (row, col): Position) -> Option<&mut T> {
+        if row < self.rows && col < self.cols {
+            self.cells.get_mut(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    /// Treats the grid as repeating forever in both directions.
+    ///
+    /// # Panics
+    ///
+    /// If the grid has no cells to repeat.
+    pub fn get_wrapped(&self, row: isize, col: isize) -> &T {
+        assert!(!self.cells.is_empty(), "an empty grid cannot wrap");
+        let row = row.rem_euclid(self.rows as isize) as usize;
+        let col = col.rem_euclid(self.cols as isize) as usize;
+        &self[(row, col)]
+    }
+
+    /// The position one step from `position` in `direction`, if it is inside
+    /// the grid.
+    pub fn step(&self, (row, col): Position, (dr, dc): Direction) -> Option<Position> {
+        let row = row as isize + dr;
+        let col = col as isize + dc;
+        if 0 <= row && row < self.rows as isize && 0 <= col && col < self.cols as isize {
+            Some((row as usize, col as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Every cell with its position, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        let cols = self.cols;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i / cols, i % cols), cell))
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// # Panics
+    ///
+    /// If `row` is not less than the number of rows.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.rows, "row {} is outside {} rows", row, self.rows);
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// # Panics
+    ///
+    /// If `col` is not less than the number of columns.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            col < self.cols,
+            "column {} is outside {} columns",
+            col,
+            self.cols
+        );
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+
+    /// Every row, including the empty rows of a grid with no columns.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.rows).map(move |row| self.row(row))
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(move |col| self.column(col))
+    }
+
+    /// The cells up, left, right and down of `position` that are in the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(position, &ORTHOGONAL)
+    }
+
+    /// The up to eight cells touching `position`, including diagonally.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours(position, &DIRECTIONS)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        position: Position,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = (Position, &'a T)> {
+        directions
+            .iter()
+            .filter_map(move |&d| self.step(position, d))
+            .map(move |p| (p, &self[p]))
+    }
+
+    /// The cells seen looking from `position` in `direction`, nearest first,
+    /// up to the edge of the grid. `position` itself is not included.
+    pub fn ray(&self, position: Position, direction: Direction) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            position,
+            direction,
+        }
+    }
+
+    /// Builds a grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// The grid turned a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for col in 0..self.cols {
+            for row in (0..self.rows).rev() {
+                cells.push(self[(row, col)].clone());
+            }
+        }
+        Self {
+            rows: self.cols,
+            cols: self.rows,
+            cells,
+        }
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        Self {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self
+                .iter_rows()
+                .flat_map(|row| row.iter().rev().cloned())
+                .collect(),
+        }
+    }
+}
+
+/// Iterator over the cells in one direction from a position.
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    position: Position,
+    direction: Direction,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.position = self.grid.step(self.position, self.direction)?;
+        Some((self.position, &self.grid[self.position]))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+/// Parses one cell per character, one row per line. Every row must be as wide
+/// as the first.
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+    Error: From<T::Error>,
+{
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let cols = text.lines().next().map_or(0, |line| line.chars().count());
+        if cols == 0 {
+            return Err(Error::parse(1, "grid has no columns"));
+        }
+
+        let mut cells = Vec::new();
+        for (row, line) in text.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                let cell = T::try_from(c).map_err(|e| Error::from(e).offset(row, col))?;
+                cells.push(cell);
+            }
+            if cells.len() != (row + 1) * cols {
+                let message = format!("expected {} columns like the first row", cols);
+                return Err(Error::parse(1, message).offset(row, 0));
+            }
+        }
+
+        Ok(Self {
+            rows: cells.len() / cols,
+            cols,
+            cells,
+        })
+    }
+}
+
+/// Writes one line per row with no trailing newline, so a grid parsed from
+/// text displays as that text.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.iter_rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abcd\nefgh\nijkl".parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.rows(), grid.cols()), (3, 4));
+        assert_eq!(grid.row(1), ['e', 'f', 'g', 'h']);
+        assert_eq!(grid.to_string(), "abcd\nefgh\nijkl");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        #[derive(Debug)]
+        struct Digit;
+
+        impl TryFrom<char> for Digit {
+            type Error = Error;
+
+            fn try_from(c: char) -> Result<Self> {
+                match c {
+                    '0'..='9' => Ok(Digit),
+                    c => Err(Error::unexpected(1, c, "a digit")),
+                }
+            }
+        }
+
+        assert_eq!(
+            "123\n4x6".parse::<Grid<Digit>>().unwrap_err().to_string(),
+            "line 2, column 2: expected a digit, found \"x\""
+        );
+        assert_eq!(
+            "123\n45".parse::<Grid<Digit>>().unwrap_err().to_string(),
+            "line 2, column 1: expected 3 columns like the first row"
+        );
+        assert!("".parse::<Grid<Digit>>().is_err());
+    }
+
+    #[test]
+    fn test_get() {
+        let grid = grid();
+        assert_eq!(grid.get((2, 3)), Some(&'l'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 4)), None);
+        assert_eq!(grid[(1, 2)], 'g');
+    }
+
+    #[test]
+    fn test_get_wrapped() {
+        let grid = grid();
+        assert_eq!(*grid.get_wrapped(0, 4), 'a');
+        assert_eq!(*grid.get_wrapped(1, 9), 'f');
+        assert_eq!(*grid.get_wrapped(-1, -1), 'l');
+    }
+
+    #[test]
+    fn test_columns() {
+        let grid = grid();
+        assert_eq!(grid.column(1).collect::<String>(), "bfj");
+        let columns: Vec<String> = grid.iter_columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, ["aei", "bfj", "cgk", "dhl"]);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        let cells = |n: Vec<(Position, &char)>| n.into_iter().map(|(_, c)| *c).collect::<String>();
+
+        assert_eq!(cells(grid.neighbours4((0, 0)).collect()), "be");
+        assert_eq!(cells(grid.neighbours4((1, 1)).collect()), "begj");
+        assert_eq!(cells(grid.neighbours8((0, 0)).collect()), "bef");
+        assert_eq!(cells(grid.neighbours8((1, 1)).collect()), "abcegijk");
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = grid();
+        let ray: String = grid.ray((0, 0), (1, 1)).map(|(_, c)| *c).collect();
+        assert_eq!(ray, "fk");
+        assert_eq!(grid.ray((1, 1), (0, 1)).last(), Some(((1, 3), &'h')));
+        assert_eq!(grid.ray((0, 0), (-1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = grid().map(|c| c.to_ascii_uppercase());
+        assert_eq!(grid.to_string(), "ABCD\nEFGH\nIJKL");
+    }
+
+    #[test]
+    fn test_rotate_and_flip() {
+        let grid = grid();
+        assert_eq!(grid.rotate_clockwise().to_string(), "iea\njfb\nkgc\nlhd");
+        assert_eq!(grid.flip_horizontal().to_string(), "dcba\nhgfe\nlkji");
+        let turned = (0..4).fold(grid.clone(), |g, _| g.rotate_clockwise());
+        assert_eq!(turned, grid);
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid: Grid<char> = Grid::new(2, 0, Vec::new());
+        assert_eq!(grid.iter_rows().count(), 2);
+        assert!(grid.iter_rows().all(<[char]>::is_empty));
+        assert_eq!(grid.iter_columns().count(), 0);
+        assert_eq!(grid.flip_horizontal(), grid);
+        assert_eq!(grid.rotate_clockwise().rows(), 0);
+        assert_eq!(grid.to_string(), "\n");
+
+        let grid: Grid<char> = Grid::new(0, 0, Vec::new());
+        assert_eq!(grid.iter_rows().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "column 4 is outside 4 columns")]
+    fn test_column_out_of_range() {
+        grid().column(4).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "column 0 is outside 0 columns")]
+    fn test_column_empty() {
+        Grid::<char>::new(2, 0, Vec::new()).column(0).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "row 3 is outside 3 rows")]
+    fn test_row_out_of_range() {
+        grid().row(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "an empty grid cannot wrap")]
+    fn test_get_wrapped_empty() {
+        Grid::<char>::new(0, 3, Vec::new()).get_wrapped(0, 0);
+    }
+}
@@ -0,0 +1,184 @@
+//! Positions on a grid of hexagons with a point at the top and bottom, so
+//! each row is offset half a hexagon from the next and every hexagon has a
+//! neighbour east, west and on the four diagonals.
+
+use crate::error::column;
+use crate::{Error, Result};
+
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// A hexagon in axial coordinates: `q` increases to the east and `r` to the
+/// south east. The third cube coordinate is `-q - r`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    pub const ORIGIN: Self = Self { q: 0, r: 0 };
+
+    pub const fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    /// The cube coordinate that makes `q + r + s == 0`.
+    pub fn s(self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn step(self, direction: HexDirection) -> Self {
+        self + direction.offset()
+    }
+
+    pub fn neighbours(self) -> impl Iterator<Item = Self> {
+        HexDirection::ALL.iter().map(move |&d| self.step(d))
+    }
+
+    /// How many steps apart two hexagons are.
+    pub fn distance(self, other: Self) -> u32 {
+        let d = Self::new(self.q - other.q, self.r - other.r);
+        (d.q.unsigned_abs() + d.r.unsigned_abs() + d.s().unsigned_abs()) / 2
+    }
+
+    /// Where following the directions from this hexagon ends up.
+    pub fn walk(self, path: &[HexDirection]) -> Self {
+        path.iter().fold(self, |hex, &d| hex.step(d))
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    /// Clockwise from east.
+    pub const ALL: [Self; 6] = [
+        Self::East,
+        Self::SouthEast,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+        Self::NorthEast,
+    ];
+
+    pub fn offset(self) -> Hex {
+        match self {
+            Self::East => Hex::new(1, 0),
+            Self::SouthEast => Hex::new(0, 1),
+            Self::SouthWest => Hex::new(-1, 1),
+            Self::West => Hex::new(-1, 0),
+            Self::NorthWest => Hex::new(0, -1),
+            Self::NorthEast => Hex::new(1, -1),
+        }
+    }
+
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            Self::East => "e",
+            Self::SouthEast => "se",
+            Self::SouthWest => "sw",
+            Self::West => "w",
+            Self::NorthWest => "nw",
+            Self::NorthEast => "ne",
+        }
+    }
+}
+
+impl fmt::Display for HexDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.abbreviation())
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|d| d.abbreviation() == s)
+            .ok_or_else(|| Error::unexpected(1, s, "e, se, sw, w, nw or ne"))
+    }
+}
+
+/// Directions run together with no separator, e.g. `esenee`.
+pub fn parse_path(s: &str) -> Result<Vec<HexDirection>> {
+    let mut path = Vec::new();
+    let mut rest = s;
+    while !rest.is_empty() {
+        let column = column(s, s.len() - rest.len());
+        let len = if rest.starts_with('n') || rest.starts_with('s') {
+            rest.char_indices().nth(2).map_or(rest.len(), |(i, _)| i)
+        } else {
+            rest.chars().next().map_or(0, char::len_utf8)
+        };
+        let direction = rest[..len]
+            .parse()
+            .map_err(|e: Error| e.offset(0, column - 1))?;
+        path.push(direction);
+        rest = &rest[len..];
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_path() {
+        use HexDirection::*;
+
+        assert_eq!(
+            parse_path("esenee").unwrap(),
+            [East, SouthEast, NorthEast, East]
+        );
+        assert_eq!(
+            parse_path("esnw").unwrap_err().to_string(),
+            "line 1, column 2: expected e, se, sw, w, nw or ne, found \"sn\""
+        );
+        assert_eq!(
+            parse_path("ex").unwrap_err().to_string(),
+            "line 1, column 2: expected e, se, sw, w, nw or ne, found \"x\""
+        );
+        assert!(parse_path("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_walk() {
+        let walk = |s: &str| Hex::ORIGIN.walk(&parse_path(s).unwrap());
+        assert_eq!(walk("nwwswee"), Hex::ORIGIN);
+        assert_eq!(walk("esew"), Hex::ORIGIN.step(HexDirection::SouthEast));
+        for &d in &HexDirection::ALL {
+            assert_eq!(Hex::ORIGIN.step(d).distance(Hex::ORIGIN), 1);
+        }
+        assert_eq!(walk("eeene").distance(Hex::ORIGIN), 4);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let hex = Hex::new(2, -1);
+        let neighbours: Vec<Hex> = hex.neighbours().collect();
+        assert_eq!(neighbours.len(), 6);
+        assert!(neighbours.iter().all(|&n| n.distance(hex) == 1));
+        assert_eq!(hex.s(), -1);
+    }
+}
@@ -1,6 +1,54 @@
 use std::fs::File;
 use std::io::Read;
-use std::io::Result;
+use std::str::FromStr;
+
+pub mod answers;
+pub mod bench;
+pub mod constraint;
+pub mod error;
+pub mod grid;
+pub mod hex;
+pub mod modular;
+pub mod output;
+pub mod record;
+pub mod scaffold;
+pub mod solution;
+pub mod source;
+pub mod store;
+#[cfg(test)]
+mod testing;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+pub use error::{Error, Result};
+pub use solution::{Part, Solution};
+
+/// The Advent of Code event these solutions are for.
+pub const YEAR: u16 = 2020;
 
 pub fn load_file_by_lines(filename: &str) -> Result<Vec<String>> {
     let contents = load_file(filename)?;
@@ -13,3 +61,67 @@ pub fn load_file(filename: &str) -> Result<String> {
     file.read_to_string(&mut contents)?;
     Ok(contents)
 }
+
+/// Parses every line of `text` with `FromStr`, reporting errors at the line they occur on.
+pub fn parse_lines<T>(text: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    Error: From<T::Err>,
+{
+    parse_lines_with(text, |line| line.parse::<T>().map_err(Error::from))
+}
+
+/// Parses every line of `text` with `f`, reporting errors at the line they occur on.
+pub fn parse_lines_with<'a, T, F>(text: &'a str, f: F) -> Result<Vec<T>>
+where
+    F: Fn(&'a str) -> Result<T>,
+{
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.offset(i, 0)))
+        .collect()
+}
+
+/// Parses each `separator`-separated field of `line` as a number, reporting
+/// errors at the column the field starts on.
+pub fn parse_separated<T>(line: &str, separator: char) -> Result<Vec<T>>
+where
+    T: FromStr<Err = std::num::ParseIntError>,
+{
+    let mut column = 1;
+    line.split(separator)
+        .map(|field| {
+            let number = error::parse_number(field, column);
+            column += field.chars().count() + 1;
+            number
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(parse_lines::<u32>("1\n2\n3").unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            parse_lines::<u32>("1\n2\nx").unwrap_err().to_string(),
+            "line 3, column 1: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_parse_separated() {
+        assert_eq!(
+            parse_separated::<u32>("7,1,14", ',').unwrap(),
+            vec![7, 1, 14]
+        );
+        assert_eq!(
+            parse_separated::<u32>("7,1,x", ',')
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 5: invalid digit found in string in \"x\""
+        );
+    }
+}
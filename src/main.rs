@@ -0,0 +1,573 @@
+use input::answers::{self, Answers, Outcome};
+use input::bench;
+use input::output::{self, Answer};
+use input::scaffold;
+use input::solution::{self, Day, Part};
+use input::source::{InputSource, Resolver};
+use input::store::Status;
+use input::Error;
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Instant;
+
+const USAGE: &str =
+    "usage: aoc run <day> [--part 1|2] [--input <path>|-] [--format text|json|junit]
+       aoc run --all [--part 1|2] [--format text|json|junit]
+       aoc verify [<day>] [--part 1|2] [--input <path>|-]
+       aoc bench [<day>] [--part 1|2] [--input <path>|-] [--iterations <n>]
+                 [--format text|json|markdown]
+       aoc inputs
+       aoc new <year> <day>
+
+inputs are read from --input, then $AOC_INPUT_DIR/day<N>/input.txt,
+then $AOC_CACHE_DIR/<year>/<N>/$AOC_PROFILE.txt, then src/day<N>/input.txt
+in this repository, using the first of these that exists
+
+verify checks answers against $AOC_ANSWERS, or answers.toml in this repository";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let command = match Command::parse(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let ok = match command {
+        Command::Run { selection, format } => run(
+            &selection.days,
+            &selection.parts,
+            &selection.resolver(),
+            format,
+        ),
+        Command::Verify(selection) => {
+            let path = env::var_os(answers::ANSWERS_VAR)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml"));
+            match Answers::load(&path) {
+                Ok(answers) => verify(
+                    &selection.days,
+                    &selection.parts,
+                    &selection.resolver(),
+                    &answers,
+                ),
+                Err(e) => {
+                    eprintln!("{}: {}", path.display(), e);
+                    false
+                }
+            }
+        }
+        Command::Bench {
+            selection,
+            iterations,
+            format,
+        } => benchmark(
+            &selection.days,
+            &selection.parts,
+            &selection.resolver(),
+            iterations,
+            format,
+        ),
+        Command::Inputs => inputs(&Resolver::from_env()),
+        Command::New { day } => new(day),
+    };
+
+    if !ok {
+        process::exit(1);
+    }
+}
+
+/// Solves each requested part of each day and prints the answers in
+/// `format`. Text is printed as each part is solved, other formats once every
+/// day has run. Failures are also reported on stderr as they happen.
+/// Returns false if any day's input could not be loaded or parsed.
+fn run(days: &[&Day], parts: &[Part], resolver: &Resolver, format: output::Format) -> bool {
+    let mut answers = Vec::new();
+    let mut emit = |answer: Answer| {
+        if format == output::Format::Text {
+            print!("{}", format.render(std::slice::from_ref(&answer)));
+        }
+        answers.push(answer);
+    };
+    let failed = |day: &Day, part: Part, message: String, input_hash: Option<String>| Answer {
+        day: day.number,
+        part,
+        answer: Err(message),
+        duration: Default::default(),
+        input_hash,
+    };
+
+    for day in days {
+        let source = resolver.resolve(day.number);
+        let text = match source.read() {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("day{}: {}", day.number, e);
+                for part in parts {
+                    emit(failed(day, *part, e.to_string(), None));
+                }
+                continue;
+            }
+        };
+        let input_hash = output::hash(&text);
+
+        for (i, part) in parts.iter().enumerate() {
+            let start = Instant::now();
+            match day.solver.solve(&text, *part) {
+                Ok(answer) => emit(Answer {
+                    day: day.number,
+                    part: *part,
+                    answer: Ok(answer),
+                    duration: start.elapsed(),
+                    input_hash: Some(input_hash.clone()),
+                }),
+                Err(e) => {
+                    let message = format!("{}: {}", source, e);
+                    eprintln!("day{}: {}", day.number, message);
+                    // Parsing failed, so every remaining part fails the same way.
+                    for part in &parts[i..] {
+                        emit(failed(
+                            day,
+                            *part,
+                            message.clone(),
+                            Some(input_hash.clone()),
+                        ));
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    if format != output::Format::Text {
+        print!("{}", format.render(&answers));
+    }
+    answers.iter().all(|a| a.answer.is_ok())
+}
+
+/// Solves each requested part and checks it against the recorded answers,
+/// printing pass, fail or missing with how long the part took. Days without
+/// an input count as missing.
+/// Returns false if any part failed or could not be solved.
+fn verify(days: &[&Day], parts: &[Part], resolver: &Resolver, answers: &Answers) -> bool {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for day in days {
+        let source = resolver.resolve(day.number);
+        let text = match source.read() {
+            Ok(text) => text,
+            // Without an input there is nothing to check.
+            Err(e @ Error::MissingInput(_)) | Err(e @ Error::EmptyInput(_)) => {
+                println!("day{}: missing input: {}", day.number, e);
+                missing += parts.len();
+                continue;
+            }
+            Err(e) => {
+                eprintln!("day{}: {}", day.number, e);
+                failed += parts.len();
+                continue;
+            }
+        };
+
+        for (i, part) in parts.iter().enumerate() {
+            let start = Instant::now();
+            let answer = match day.solver.solve(&text, *part) {
+                Ok(answer) => answer,
+                Err(e) => {
+                    eprintln!("day{}: {}: {}", day.number, source, e);
+                    failed += parts.len() - i;
+                    break;
+                }
+            };
+            let elapsed = start.elapsed();
+
+            let outcome = answers.check(day.number, *part, answer.as_deref());
+            match outcome {
+                Outcome::Pass => passed += 1,
+                Outcome::Fail { .. } => failed += 1,
+                Outcome::Missing => missing += 1,
+            }
+            println!(
+                "day{} part{}: {} ({:.2?})",
+                day.number,
+                part.number(),
+                outcome,
+                elapsed
+            );
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    failed == 0
+}
+
+/// Times parsing and each requested part of each day separately and prints
+/// min/median/max wall times once every day has run.
+/// Returns false if any day's input could not be loaded or parsed.
+fn benchmark(
+    days: &[&Day],
+    parts: &[Part],
+    resolver: &Resolver,
+    iterations: usize,
+    format: bench::Format,
+) -> bool {
+    let mut ok = true;
+    let mut reports = Vec::new();
+
+    for day in days {
+        let source = resolver.resolve(day.number);
+        let result = source
+            .read()
+            .and_then(|text| bench::run(day, &text, parts, iterations));
+        match result {
+            Ok(mut day_reports) => reports.append(&mut day_reports),
+            Err(e) => {
+                eprintln!("day{}: {}: {}", day.number, source, e);
+                ok = false;
+            }
+        }
+    }
+
+    print!("{}", format.render(&reports));
+    ok
+}
+
+/// Lists where each day's input would be read from and whether it is usable.
+/// Returns false if any day has no usable input.
+fn inputs(resolver: &Resolver) -> bool {
+    let mut ok = true;
+
+    for day in solution::days() {
+        match resolver.resolve(day.number) {
+            InputSource::Path(path) => {
+                let status = Status::of(&path);
+                ok &= status == Status::Present;
+                println!("day{}: {} {}", day.number, status, path.display());
+            }
+            InputSource::Stdin => println!("day{}: <stdin>", day.number),
+        }
+    }
+
+    ok
+}
+
+/// Scaffolds a new day in this repository, printing each file written.
+fn new(day: u8) -> bool {
+    match scaffold::create(Path::new(env!("CARGO_MANIFEST_DIR")), day) {
+        Ok(written) => {
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            true
+        }
+        Err(e) => {
+            eprintln!("day{}: {}", day, e);
+            false
+        }
+    }
+}
+
+enum Command {
+    Run {
+        selection: Selection,
+        format: output::Format,
+    },
+    Verify(Selection),
+    Bench {
+        selection: Selection,
+        iterations: usize,
+        format: bench::Format,
+    },
+    Inputs,
+    New {
+        day: u8,
+    },
+}
+
+/// Which days and parts to solve, and where to read the input from.
+struct Selection {
+    days: Vec<&'static Day>,
+    parts: Vec<Part>,
+    input: Option<String>,
+}
+
+impl Command {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+        if args.is_empty() {
+            return Err("missing command".to_owned());
+        }
+
+        match args.remove(0) {
+            "run" => {
+                let format = match take_value(&mut args, "--format")? {
+                    Some(format) => format.parse().map_err(|e| format!("bad format: {}", e))?,
+                    None => output::Format::Text,
+                };
+                match Selection::parse(args.into_iter())? {
+                    Selection { days, .. } if days.is_empty() => {
+                        Err("run needs a day or --all".to_owned())
+                    }
+                    selection => Ok(Self::Run { selection, format }),
+                }
+            }
+            "verify" => Ok(Self::Verify(Selection::parse_or_all(args.into_iter())?)),
+            "bench" => {
+                let iterations = match take_value(&mut args, "--iterations")? {
+                    Some(n) => match n.parse() {
+                        Ok(n) if n > 0 => n,
+                        _ => return Err("bad iteration count".to_owned()),
+                    },
+                    None => 10,
+                };
+                let format = match take_value(&mut args, "--format")? {
+                    Some(format) => format.parse().map_err(|e| format!("bad format: {}", e))?,
+                    None => bench::Format::Text,
+                };
+                Ok(Self::Bench {
+                    selection: Selection::parse_or_all(args.into_iter())?,
+                    iterations,
+                    format,
+                })
+            }
+            "inputs" => match args.first() {
+                Some(arg) => Err(format!("unexpected argument: {}", arg)),
+                None => Ok(Self::Inputs),
+            },
+            "new" => {
+                let (year, day) = match args.as_slice() {
+                    [year, day] => (*year, *day),
+                    _ => return Err("new needs a year and a day".to_owned()),
+                };
+                if year.parse() != Ok(input::YEAR) {
+                    return Err(format!("this repository only holds {}", input::YEAR));
+                }
+                match day.parse() {
+                    Ok(day) if (1..=25).contains(&day) => Ok(Self::New { day }),
+                    _ => Err(format!("bad day: {}", day)),
+                }
+            }
+            other => Err(format!("unknown command: {}", other)),
+        }
+    }
+}
+
+/// Removes `flag` and the value after it from `args`, returning the value.
+fn take_value<'a>(args: &mut Vec<&'a str>, flag: &str) -> Result<Option<&'a str>, String> {
+    match args.iter().position(|a| *a == flag) {
+        Some(i) if i + 1 < args.len() => {
+            let value = args.remove(i + 1);
+            args.remove(i);
+            Ok(Some(value))
+        }
+        Some(_) => Err(format!("{} needs a value", flag)),
+        None => Ok(None),
+    }
+}
+
+impl Selection {
+    /// Like `parse`, but selects every day when none is given.
+    fn parse_or_all<'a>(args: impl Iterator<Item = &'a str>) -> Result<Self, String> {
+        let mut selection = Self::parse(args)?;
+        if selection.days.is_empty() {
+            selection.days = solution::days().iter().collect();
+        }
+        Ok(selection)
+    }
+
+    /// Parses the options shared by `run`, `verify` and `bench`. `days` is left empty
+    /// when neither a day nor `--all` was given.
+    fn parse<'a>(mut args: impl Iterator<Item = &'a str>) -> Result<Self, String> {
+        let mut days = Vec::new();
+        let mut parts = Part::BOTH.to_vec();
+        let mut input = None;
+
+        while let Some(arg) = args.next() {
+            match arg {
+                "--all" => days = solution::days().iter().collect(),
+                "--input" => match args.next() {
+                    Some(path) => input = Some(path.to_owned()),
+                    None => return Err("--input needs a path".to_owned()),
+                },
+                "--part" => {
+                    parts = match args.next() {
+                        Some("1") => vec![Part::One],
+                        Some("2") => vec![Part::Two],
+                        Some(p) => return Err(format!("bad part: {}", p)),
+                        None => return Err("--part needs a value".to_owned()),
+                    }
+                }
+                day => {
+                    let number: u8 = day.parse().map_err(|_| format!("bad day: {}", day))?;
+                    let day = solution::day(number)
+                        .ok_or_else(|| format!("day {} is not implemented", number))?;
+                    days = vec![day];
+                }
+            }
+        }
+
+        if input.is_some() && days.len() != 1 {
+            return Err("--input needs a single day".to_owned());
+        }
+
+        Ok(Self { days, parts, input })
+    }
+
+    fn resolver(&self) -> Resolver {
+        let resolver = Resolver::from_env();
+        match &self.input {
+            Some(input) => resolver.with_input(input.clone()),
+            None => resolver,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        Command::parse(&args)
+    }
+
+    fn parse_run(args: &[&str]) -> Selection {
+        match parse(args).unwrap() {
+            Command::Run { selection, .. } => selection,
+            _ => panic!("not a run command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_run_day() {
+        let Selection { days, parts, input } = parse_run(&["run", "3"]);
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].number, 3);
+        assert_eq!(parts, vec![Part::One, Part::Two]);
+        assert_eq!(input, None);
+    }
+
+    #[test]
+    fn test_parse_run_input() {
+        let selection = parse_run(&["run", "3", "--input", "-"]);
+        assert_eq!(selection.input.as_deref(), Some("-"));
+    }
+
+    #[test]
+    fn test_parse_run_part() {
+        let selection = parse_run(&["run", "3", "--part", "2"]);
+        assert_eq!(selection.parts, vec![Part::Two]);
+    }
+
+    #[test]
+    fn test_parse_run_format() {
+        match parse(&["run", "--format", "junit", "--all"]).unwrap() {
+            Command::Run { selection, format } => {
+                assert_eq!(selection.days.len(), solution::days().len());
+                assert_eq!(format, output::Format::Junit);
+            }
+            _ => panic!("not a run command"),
+        }
+        match parse(&["run", "1", "--format", "xml"]) {
+            Err(e) => assert_eq!(e, "bad format: expected text, json or junit, found \"xml\""),
+            Ok(_) => panic!("xml is not a format"),
+        }
+        assert!(parse(&["run", "1", "--format"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_all() {
+        let selection = parse_run(&["run", "--all"]);
+        assert_eq!(selection.days.len(), solution::days().len());
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        match parse(&["verify"]).unwrap() {
+            Command::Verify(selection) => {
+                assert_eq!(selection.days.len(), solution::days().len())
+            }
+            _ => panic!("not a verify command"),
+        }
+        match parse(&["verify", "10", "--part", "2"]).unwrap() {
+            Command::Verify(selection) => {
+                assert_eq!(selection.days[0].number, 10);
+                assert_eq!(selection.parts, vec![Part::Two]);
+            }
+            _ => panic!("not a verify command"),
+        }
+        assert!(parse(&["verify", "--input", "x.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        match parse(&["bench", "15", "--iterations", "3", "--format", "json"]).unwrap() {
+            Command::Bench {
+                selection,
+                iterations,
+                format,
+            } => {
+                assert_eq!(selection.days[0].number, 15);
+                assert_eq!(iterations, 3);
+                assert_eq!(format, bench::Format::Json);
+            }
+            _ => panic!("not a bench command"),
+        }
+        match parse(&["bench"]).unwrap() {
+            Command::Bench {
+                selection,
+                iterations,
+                format,
+            } => {
+                assert_eq!(selection.days.len(), solution::days().len());
+                assert_eq!(iterations, 10);
+                assert_eq!(format, bench::Format::Text);
+            }
+            _ => panic!("not a bench command"),
+        }
+        assert!(parse(&["bench", "--iterations", "0"]).is_err());
+        assert!(parse(&["bench", "--iterations"]).is_err());
+        assert!(parse(&["bench", "--format", "csv"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert!(matches!(
+            parse(&["new", "2020", "13"]),
+            Ok(Command::New { day: 13 })
+        ));
+        assert!(parse(&["new", "2019", "13"]).is_err());
+        assert!(parse(&["new", "2020", "26"]).is_err());
+        assert!(parse(&["new", "2020"]).is_err());
+        assert!(parse(&["new", "2020", "1", "2"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_inputs() {
+        assert!(matches!(parse(&["inputs"]), Ok(Command::Inputs)));
+        assert!(parse(&["inputs", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["walk", "1"]).is_err());
+        assert!(parse(&["run"]).is_err());
+        assert!(parse(&["run", "x"]).is_err());
+        assert!(parse(&["run", "26"]).is_err());
+        assert!(parse(&["run", "1", "--part", "3"]).is_err());
+        assert!(parse(&["run", "1", "--input"]).is_err());
+        assert!(parse(&["run", "--all", "--input", "x.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_answers_file_parses() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml");
+        assert!(Answers::load(&path).is_ok());
+    }
+}
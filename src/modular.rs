@@ -0,0 +1,426 @@
+//! Modular arithmetic on `u128` that never overflows for moduli up to
+//! `u128::MAX`, and the Chinese Remainder Theorem and discrete logarithms
+//! built on it.
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Greatest common divisor, with `gcd(0, 0) == 0`.
+pub fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns `(g, x, y)` with `a * x + b * y == g`, where `g` is the
+/// non-negative greatest common divisor of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// `(a + b) % m` for `a, b < m`.
+pub fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `(a - b) % m` for `a, b < m`, wrapping below zero.
+pub fn sub_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= b {
+        a - b
+    } else {
+        m - (b - a)
+    }
+}
+
+/// `(a * b) % m`, falling back to shift-and-add when the product would
+/// overflow.
+pub fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    let (mut a, mut b) = (a % m, b % m);
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    let mut product = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            product = add_mod(product, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    product
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: u128, m: u128) -> Option<u128> {
+    if m == 0 {
+        return None;
+    }
+
+    // Extended Euclid, keeping only the coefficient of `a` and keeping it
+    // reduced mod `m` so it never goes negative.
+    let (mut r0, mut r1) = (m, a % m);
+    let (mut t0, mut t1) = (0, 1 % m);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, sub_mod(t0, mul_mod(q, t1, m), m));
+    }
+
+    if r0 == 1 {
+        Some(t0)
+    } else {
+        None
+    }
+}
+
+/// `base.pow(exponent) % m`, by repeated squaring.
+pub fn pow_mod(base: u128, mut exponent: u128, m: u128) -> u128 {
+    let mut base = base % m;
+    let mut result = 1 % m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// The smallest `x` with `pow_mod(base, x, m) == target % m`, if there is
+/// one, found by baby-step giant-step.
+///
+/// Keeps a table of about `sqrt(m)` entries, so `m` should comfortably fit
+/// that in memory.
+pub fn discrete_log(base: u128, target: u128, m: u128) -> Option<u128> {
+    if m == 1 {
+        return Some(0);
+    }
+    let target = target % m;
+
+    // Try small exponents first. Each factor `base` shares with `m` is
+    // divided out below at least halving `m`, so this covers every answer
+    // smaller than the number of times that happens.
+    let mut power = 1;
+    for x in 0..u128::BITS {
+        if power == target {
+            return Some(x.into());
+        }
+        power = mul_mod(power, base, m);
+    }
+
+    // Any answer is now at least `reduced`, and base^x ≡ target (mod m)
+    // exactly when coefficient * base^(x - reduced) ≡ target' (mod m').
+    let (mut m, mut target, mut coefficient, mut reduced) = (m, target, 1, 0);
+    loop {
+        let g = gcd(base, m);
+        if g == 1 {
+            break;
+        }
+        if target % g != 0 {
+            return None;
+        }
+        target /= g;
+        m /= g;
+        coefficient = mul_mod(coefficient, base / g, m);
+        reduced += 1;
+    }
+
+    let target = mul_mod(target, mod_inverse(coefficient, m)?, m);
+    coprime_discrete_log(base, target, m).map(|x| x + reduced)
+}
+
+/// `discrete_log` for a `base` with an inverse modulo `m`.
+fn coprime_discrete_log(base: u128, target: u128, m: u128) -> Option<u128> {
+    if m == 1 {
+        return Some(0);
+    }
+    let steps = {
+        let root = m.isqrt();
+        if root * root == m {
+            root
+        } else {
+            root + 1
+        }
+    };
+
+    // Baby steps: base^j for every j < steps, keeping the smallest j.
+    let mut baby = HashMap::new();
+    let mut power = 1;
+    for j in 0..steps {
+        baby.entry(power).or_insert(j);
+        power = mul_mod(power, base, m);
+    }
+
+    // Giant steps: target * base^(-steps * i) until it is a baby step.
+    let giant = mod_inverse(pow_mod(base, steps, m), m)?;
+    let mut gamma = target;
+    for i in 0..steps {
+        if let Some(&j) = baby.get(&gamma) {
+            return Some(i * steps + j);
+        }
+        gamma = mul_mod(gamma, giant, m);
+    }
+    None
+}
+
+/// `x ≡ residue (mod modulus)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Congruence {
+    pub residue: u128,
+    pub modulus: u128,
+}
+
+impl Congruence {
+    /// Reduces `residue` so that it is less than `modulus`.
+    ///
+    /// # Panics
+    ///
+    /// If `modulus` is zero.
+    pub fn new(residue: u128, modulus: u128) -> Self {
+        assert!(modulus > 0, "congruence modulo zero");
+        Self {
+            residue: residue % modulus,
+            modulus,
+        }
+    }
+
+    pub fn is_satisfied_by(&self, x: u128) -> bool {
+        x % self.modulus == self.residue
+    }
+}
+
+impl fmt::Display for Congruence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "x ≡ {} (mod {})", self.residue, self.modulus)
+    }
+}
+
+/// Why a system of congruences could not be solved.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CrtError {
+    /// The moduli share a factor, which `crt_sieve` does not handle.
+    NotCoprime(u128, u128),
+    /// The moduli share a factor and the congruences disagree modulo it, so
+    /// there is no solution.
+    Incompatible(Congruence, Congruence),
+    /// The combined modulus does not fit in a `u128`.
+    Overflow,
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotCoprime(a, b) => write!(f, "moduli {} and {} are not coprime", a, b),
+            Self::Incompatible(a, b) => write!(f, "{} and {} have no common solution", a, b),
+            Self::Overflow => write!(f, "combined modulus overflows u128"),
+        }
+    }
+}
+
+impl std::error::Error for CrtError {}
+
+/// Solves a system of congruences with pairwise coprime moduli by stepping
+/// through candidates, which is simple but only fast for small moduli.
+///
+/// Returns the smallest non-negative solution and the product of the moduli.
+/// An empty system is solved by every number.
+pub fn crt_sieve(congruences: &[Congruence]) -> Result<Congruence, CrtError> {
+    let mut solution = Congruence::new(0, 1);
+    for c in congruences {
+        if gcd(solution.modulus, c.modulus) != 1 {
+            return Err(CrtError::NotCoprime(solution.modulus, c.modulus));
+        }
+        let mut x = solution.residue;
+        while !c.is_satisfied_by(x) {
+            x = x.checked_add(solution.modulus).ok_or(CrtError::Overflow)?;
+        }
+        let modulus = solution
+            .modulus
+            .checked_mul(c.modulus)
+            .ok_or(CrtError::Overflow)?;
+        solution = Congruence::new(x, modulus);
+    }
+    Ok(solution)
+}
+
+/// Solves a system of congruences whose moduli need not be coprime.
+///
+/// Returns the smallest non-negative solution and the least common multiple
+/// of the moduli, or why there is none. An empty system is solved by every
+/// number.
+pub fn crt(congruences: &[Congruence]) -> Result<Congruence, CrtError> {
+    congruences
+        .iter()
+        .try_fold(Congruence::new(0, 1), |a, b| merge(a, *b))
+}
+
+/// Combines two congruences into one that holds exactly when both do.
+fn merge(a: Congruence, b: Congruence) -> Result<Congruence, CrtError> {
+    let g = gcd(a.modulus, b.modulus);
+    let difference = sub_mod(b.residue, a.residue % b.modulus, b.modulus);
+    if !difference.is_multiple_of(g) {
+        return Err(CrtError::Incompatible(a, b));
+    }
+
+    // x = a.residue + a.modulus * k, where
+    // a.modulus * k ≡ difference (mod b.modulus), so dividing through by g,
+    // k ≡ (difference / g) * inverse(a.modulus / g) (mod b.modulus / g).
+    let step = b.modulus / g;
+    let lcm = (a.modulus / g)
+        .checked_mul(b.modulus)
+        .ok_or(CrtError::Overflow)?;
+    let inverse = mod_inverse(a.modulus / g, step).expect("a.modulus / g is coprime to step");
+    let k = mul_mod(difference / g, inverse, step);
+
+    // a.modulus * k < a.modulus * step == lcm, so this cannot overflow.
+    Ok(Congruence::new(add_mod(a.residue, a.modulus * k, lcm), lcm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(17, 5), 1);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for &(a, b) in &[(240, 46), (46, 240), (-12, 18), (7, 0), (0, 0), (17, -5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i128);
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_mul_mod() {
+        assert_eq!(mul_mod(7, 8, 5), 1);
+        let m = u128::MAX - 158; // a large prime
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+        assert_eq!(mul_mod(u128::MAX, 2, u128::MAX - 1), 2);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(5, 1), Some(0));
+        assert_eq!(mod_inverse(5, 0), None);
+
+        let m = u128::MAX - 158;
+        let inverse = mod_inverse(m - 2, m).unwrap();
+        assert_eq!(mul_mod(m - 2, inverse, m), 1);
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        assert_eq!(pow_mod(7, 8, 20201227), 5764801);
+        assert_eq!(pow_mod(2, 10, 1000), 24);
+        assert_eq!(pow_mod(5, 0, 13), 1);
+        assert_eq!(pow_mod(5, 0, 1), 0);
+        let m = u128::MAX - 158;
+        assert_eq!(pow_mod(3, m - 1, m), 1);
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+        assert_eq!(discrete_log(3, 1, 7), Some(0));
+        // 2 only generates 1, 2 and 4 modulo 7.
+        assert_eq!(discrete_log(2, 3, 7), None);
+        // 2 has no inverse modulo 8, but its powers still reach 4 and 0.
+        assert_eq!(discrete_log(2, 4, 8), Some(2));
+        assert_eq!(discrete_log(2, 0, 8), Some(3));
+        assert_eq!(discrete_log(2, 6, 8), None);
+        assert_eq!(discrete_log(6, 0, 8), Some(3));
+
+        // Check against every power for moduli that share factors with the
+        // base, where answers can come after many reductions.
+        for m in 1..40 {
+            for base in 0..m {
+                for target in 0..m {
+                    let expected = (0..2 * m).find(|&x| pow_mod(base, x, m) == target);
+                    assert_eq!(
+                        discrete_log(base, target, m),
+                        expected,
+                        "{}^x = {} mod {}",
+                        base,
+                        target,
+                        m
+                    );
+                }
+            }
+        }
+
+        for x in [0, 1, 99, 12345, 20201225] {
+            let target = pow_mod(7, x, 20201227);
+            assert_eq!(discrete_log(7, target, 20201227), Some(x));
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        let system = [
+            Congruence::new(2, 3),
+            Congruence::new(3, 5),
+            Congruence::new(2, 7),
+        ];
+        assert_eq!(crt(&system), Ok(Congruence::new(23, 105)));
+        assert_eq!(crt_sieve(&system), Ok(Congruence::new(23, 105)));
+        assert_eq!(crt(&[]), Ok(Congruence::new(0, 1)));
+    }
+
+    #[test]
+    fn test_crt_not_coprime() {
+        let system = [Congruence::new(2, 6), Congruence::new(8, 10)];
+        assert_eq!(crt(&system), Ok(Congruence::new(8, 30)));
+        assert_eq!(crt_sieve(&system), Err(CrtError::NotCoprime(6, 10)));
+
+        let system = [Congruence::new(1, 6), Congruence::new(2, 10)];
+        assert_eq!(
+            crt(&system).unwrap_err().to_string(),
+            "x ≡ 1 (mod 6) and x ≡ 2 (mod 10) have no common solution"
+        );
+    }
+
+    #[test]
+    fn test_crt_large() {
+        // Primes whose product needs more than 64 bits.
+        let p = 18_446_744_073_709_551_557;
+        let q = 4_294_967_291;
+        let system = [Congruence::new(5, p), Congruence::new(7, q)];
+        let solution = crt(&system).unwrap();
+        assert_eq!(solution.modulus, p * q);
+        assert!(system.iter().all(|c| c.is_satisfied_by(solution.residue)));
+
+        let system = [Congruence::new(1, u128::MAX), Congruence::new(1, 2)];
+        assert_eq!(crt(&system), Err(CrtError::Overflow));
+    }
+}
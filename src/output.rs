@@ -0,0 +1,303 @@
+use crate::Part;
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// What solving one part of one day produced.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: u8,
+    pub part: Part,
+    /// The answer, `None` if the solver has none, or why solving failed.
+    pub answer: std::result::Result<Option<String>, String>,
+    pub duration: Duration,
+    /// `hash` of the input text, if it could be read.
+    pub input_hash: Option<String>,
+}
+
+/// A 64-bit FNV-1a hash of `text` in hex, to tell which input an answer is for
+/// without publishing the input itself.
+pub fn hash(text: &str) -> String {
+    let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// How answers are written out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Junit,
+}
+
+/// A `--format` value that names no format. Unlike puzzle input errors it has
+/// no line or column, as it comes from the command line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnknownFormat {
+    pub found: String,
+    pub expected: &'static str,
+}
+
+impl fmt::Display for UnknownFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {}, found {:?}", self.expected, self.found)
+    }
+}
+
+impl std::error::Error for UnknownFormat {}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "junit" => Ok(Self::Junit),
+            other => Err(UnknownFormat {
+                found: other.to_owned(),
+                expected: "text, json or junit",
+            }),
+        }
+    }
+}
+
+impl Format {
+    pub fn render(self, answers: &[Answer]) -> String {
+        match self {
+            Self::Text => text(answers),
+            Self::Json => json(answers),
+            Self::Junit => junit(answers),
+        }
+    }
+}
+
+/// One `day<N> part<N>: <answer>` line per answer. Failures are left out, as
+/// the runner reports them on stderr.
+fn text(answers: &[Answer]) -> String {
+    let mut out = String::new();
+    for a in answers {
+        // Writing to a String cannot fail.
+        let _ = match &a.answer {
+            Ok(Some(answer)) => writeln!(out, "day{} part{}: {}", a.day, a.part.number(), answer),
+            Ok(None) => writeln!(out, "day{} part{}: no answer", a.day, a.part.number()),
+            Err(_) => Ok(()),
+        };
+    }
+    out
+}
+
+/// A JSON array with one object per answer. Durations are in nanoseconds;
+/// `answer`, `error` and `input_hash` are `null` when absent.
+fn json(answers: &[Answer]) -> String {
+    let optional = |s: Option<&str>| s.map_or_else(|| "null".to_owned(), json_string);
+
+    json_array(answers.iter().map(|a| {
+        let (answer, error) = match &a.answer {
+            Ok(answer) => (answer.as_deref(), None),
+            Err(e) => (None, Some(e.as_str())),
+        };
+        json_object(&[
+            ("day", a.day.to_string()),
+            ("part", a.part.number().to_string()),
+            ("answer", optional(answer)),
+            ("error", optional(error)),
+            ("duration_ns", a.duration.as_nanos().to_string()),
+            ("input_hash", optional(a.input_hash.as_deref())),
+        ])
+    }))
+}
+
+/// A JSON array with one object per line, as both `--format json` outputs
+/// write it.
+pub(crate) fn json_array(objects: impl Iterator<Item = String>) -> String {
+    let objects: Vec<String> = objects.map(|o| format!("  {}", o)).collect();
+    if objects.is_empty() {
+        "[]\n".to_owned()
+    } else {
+        format!("[\n{}\n]\n", objects.join(",\n"))
+    }
+}
+
+/// A JSON object of `fields` in order, whose values are already JSON.
+pub(crate) fn json_object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{}: {}", json_string(name), value))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A JUnit test suite with one test case per answer. Failed parts are errors
+/// and parts without an answer are skipped.
+fn junit(answers: &[Answer]) -> String {
+    let errors = answers.iter().filter(|a| a.answer.is_err()).count();
+    let skipped = answers.iter().filter(|a| a.answer == Ok(None)).count();
+    let total: Duration = answers.iter().map(|a| a.duration).sum();
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuite name=\"aoc{}\" tests=\"{}\" failures=\"0\" errors=\"{}\" \
+         skipped=\"{}\" time=\"{:.6}\">",
+        crate::YEAR,
+        answers.len(),
+        errors,
+        skipped,
+        total.as_secs_f64()
+    );
+    for a in answers {
+        let _ = write!(
+            out,
+            "  <testcase classname=\"day{}\" name=\"part{}\" time=\"{:.6}\">",
+            a.day,
+            a.part.number(),
+            a.duration.as_secs_f64()
+        );
+        let _ = match &a.answer {
+            Ok(Some(answer)) => write!(out, "<system-out>{}</system-out>", xml_escape(answer)),
+            Ok(None) => write!(out, "<skipped message=\"no answer\"/>"),
+            Err(e) => write!(out, "<error message=\"{}\"/>", xml_escape(e)),
+        };
+        out.push_str("</testcase>\n");
+    }
+    out.push_str("</testsuite>\n");
+    out
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers() -> Vec<Answer> {
+        vec![
+            Answer {
+                day: 1,
+                part: Part::One,
+                answer: Ok(Some("514579".to_owned())),
+                duration: Duration::from_micros(1500),
+                input_hash: Some(hash("1721\n979")),
+            },
+            Answer {
+                day: 11,
+                part: Part::Two,
+                answer: Ok(None),
+                duration: Duration::from_micros(20),
+                input_hash: Some(hash("L.L")),
+            },
+            Answer {
+                day: 2,
+                part: Part::One,
+                answer: Err("src/day2/input.txt: \"no\" <input>".to_owned()),
+                duration: Duration::default(),
+                input_hash: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(""), "cbf29ce484222325");
+        assert_eq!(hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(hash("1\n2"), hash("1\n3"));
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            Format::Text.render(&answers()),
+            "day1 part1: 514579\nday11 part2: no answer\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(Format::Json.render(&[]), "[]\n");
+        let json = Format::Json.render(&answers());
+        let lines: Vec<&str> = json.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[1],
+            format!(
+                "  {{\"day\": 1, \"part\": 1, \"answer\": \"514579\", \"error\": null, \
+                 \"duration_ns\": 1500000, \"input_hash\": \"{}\"}},",
+                hash("1721\n979")
+            )
+        );
+        assert_eq!(
+            lines[3],
+            "  {\"day\": 2, \"part\": 1, \"answer\": null, \
+             \"error\": \"src/day2/input.txt: \\\"no\\\" <input>\", \
+             \"duration_ns\": 0, \"input_hash\": null}"
+        );
+    }
+
+    #[test]
+    fn test_junit() {
+        let xml = Format::Junit.render(&answers());
+        let lines: Vec<&str> = xml.lines().collect();
+        assert_eq!(
+            lines[1],
+            "<testsuite name=\"aoc2020\" tests=\"3\" failures=\"0\" errors=\"1\" \
+             skipped=\"1\" time=\"0.001520\">"
+        );
+        assert_eq!(
+            lines[2],
+            "  <testcase classname=\"day1\" name=\"part1\" time=\"0.001500\">\
+             <system-out>514579</system-out></testcase>"
+        );
+        assert!(lines[3].contains("<skipped message=\"no answer\"/>"));
+        assert!(lines[4]
+            .contains("<error message=\"src/day2/input.txt: &quot;no&quot; &lt;input&gt;\"/>"));
+        assert_eq!(lines[5], "</testsuite>");
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("junit".parse::<Format>().unwrap(), Format::Junit);
+        assert_eq!(
+            "xml".parse::<Format>().unwrap_err().to_string(),
+            "expected text, json or junit, found \"xml\""
+        );
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a\"b\\c\n\u{1}"), "\"a\\\"b\\\\c\\n\\u0001\"");
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{Error, Result};
+
+/// One non-blank line of a record, with its indentation and trailing
+/// whitespace (including any `\r`) removed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Line<'a> {
+    /// 1-based line number in the whole input.
+    pub number: usize,
+    /// How many characters of indentation were removed.
+    pub indent: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Moves an error reported relative to this line's text to where the line
+    /// sits in the whole input.
+    pub fn locate(&self, e: Error) -> Error {
+        e.offset(self.number - 1, self.indent)
+    }
+
+    /// Parses the line with `f`, reporting errors where the line sits in the
+    /// whole input.
+    pub fn parse_with<T>(&self, f: impl FnOnce(&'a str) -> Result<T>) -> Result<T> {
+        f(self.text).map_err(|e| self.locate(e))
+    }
+}
+
+/// A run of lines separated from the next by one or more blank lines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record<'a> {
+    lines: Vec<Line<'a>>,
+}
+
+impl<'a> Record<'a> {
+    /// 1-based line number of the record's first line in the whole input.
+    pub fn line(&self) -> usize {
+        self.lines[0].number
+    }
+
+    pub fn lines(&self) -> &[Line<'a>] {
+        &self.lines
+    }
+
+    /// The text of each line, without indentation.
+    pub fn texts(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.lines.iter().map(|line| line.text)
+    }
+
+    /// Parses every line of the record with `f`, reporting errors at the line
+    /// they occur on in the whole input.
+    pub fn parse_lines_with<T, F>(&self, f: F) -> Result<Vec<T>>
+    where
+        F: Fn(&'a str) -> Result<T>,
+    {
+        self.lines.iter().map(|line| line.parse_with(&f)).collect()
+    }
+}
+
+/// Splits `text` into blank-line-separated records. Windows line endings,
+/// trailing whitespace, indentation and extra blank lines between, before or
+/// after records are all ignored.
+pub fn split(text: &str) -> Vec<Record<'_>> {
+    let mut records = Vec::new();
+    let mut lines = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            if !lines.is_empty() {
+                records.push(Record { lines });
+                lines = Vec::new();
+            }
+            continue;
+        }
+
+        lines.push(Line {
+            number: i + 1,
+            indent: line.chars().take_while(|c| c.is_whitespace()).count(),
+            text: trimmed,
+        });
+    }
+    if !lines.is_empty() {
+        records.push(Record { lines });
+    }
+
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let records = split("\nab\ncd\n\n\n  ef  \n\n");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].texts().collect::<Vec<_>>(), ["ab", "cd"]);
+        assert_eq!(records[0].line(), 2);
+        assert_eq!(records[1].texts().collect::<Vec<_>>(), ["ef"]);
+        assert_eq!(records[1].line(), 6);
+        assert_eq!(records[1].lines()[0].indent, 2);
+
+        // an ideographic space is one character but three bytes
+        let records = split("\u{3000}\tab");
+        assert_eq!(records[0].lines()[0].indent, 2);
+        assert_eq!(records[0].texts().collect::<Vec<_>>(), ["ab"]);
+    }
+
+    #[test]
+    fn test_split_crlf() {
+        let records = split("ab\r\ncd\r\n\r\nef\r\n");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].texts().collect::<Vec<_>>(), ["ab", "cd"]);
+        assert_eq!(records[1].texts().collect::<Vec<_>>(), ["ef"]);
+        assert_eq!(records[1].line(), 4);
+    }
+
+    #[test]
+    fn test_split_empty() {
+        assert!(split("").is_empty());
+        assert!(split("\n \n\t\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_lines_with() {
+        let records = split("1\n2\n\n3\n    x");
+        assert_eq!(
+            records[0]
+                .parse_lines_with(|l| Ok(l.parse::<u32>()?))
+                .unwrap(),
+            [1, 2]
+        );
+        assert_eq!(
+            records[1]
+                .parse_lines_with(|l| crate::error::parse_number::<u32>(l, 1))
+                .unwrap_err()
+                .to_string(),
+            "line 5, column 5: invalid digit found in string in \"x\""
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use crate::Result;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The solver module a new day starts from: a `Solution` that parses the input
+/// into lines, `part1`/`part2` stubs and a test skeleton for the example.
+pub fn module(day: u8) -> String {
+    format!(
+        r#"use crate::{{Result, Solution}};
+
+pub struct Day{0};
+
+impl Solution for Day{0} {{
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>> {{
+        Ok(text.lines().collect())
+    }}
+
+    fn part1(&self, lines: &Self::Input<'_>) -> Option<String> {{
+        part1(lines).map(|answer| answer.to_string())
+    }}
+
+    fn part2(&self, lines: &Self::Input<'_>) -> Option<String> {{
+        part2(lines).map(|answer| answer.to_string())
+    }}
+}}
+
+pub fn part1(_lines: &[&str]) -> Option<usize> {{
+    None
+}}
+
+pub fn part2(_lines: &[&str]) -> Option<usize> {{
+    None
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    fn test_part1() {{
+        let lines = Day{0}.parse(EXAMPLE).unwrap();
+
+        assert_eq!(part1(&lines), None);
+    }}
+
+    #[test]
+    fn test_part2() {{
+        let lines = Day{0}.parse(EXAMPLE).unwrap();
+
+        assert_eq!(part2(&lines), None);
+    }}
+}}
+"#,
+        day
+    )
+}
+
+/// Adds `pub mod day<N>;` to `src/lib.rs`, keeping the declarations in the
+/// order rustfmt sorts them. Returns `None` if it is already declared.
+pub fn declare_module(lib: &str, day: u8) -> Option<String> {
+    let declaration = format!("pub mod day{};", day);
+    let mut lines: Vec<&str> = lib.lines().collect();
+    if lines.contains(&declaration.as_str()) {
+        return None;
+    }
+
+    let name = format!("day{}", day);
+    let days: Vec<(usize, &str)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| Some((i, l.strip_prefix("pub mod ")?.strip_suffix(';')?)))
+        .filter(|(_, module)| module.starts_with("day"))
+        .collect();
+    let at = match days.iter().find(|(_, module)| *module > name.as_str()) {
+        Some((i, _)) => *i,
+        None => days.last().map_or(0, |(i, _)| i + 1),
+    };
+    lines.insert(at, declaration.as_str());
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Why a day could not be added to the `DAYS` registry.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RegisterError {
+    /// The day is registered already, so there is nothing to do.
+    AlreadyRegistered,
+    /// There is no `static DAYS: &[Day] = &[ ... ];` to add it to.
+    MissingRegistry,
+    /// An entry's `number` field is not a day number.
+    UnreadableEntry(String),
+}
+
+impl fmt::Display for RegisterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadyRegistered => write!(f, "day is already registered"),
+            Self::MissingRegistry => write!(f, "no `static DAYS: &[Day]` registry found"),
+            Self::UnreadableEntry(entry) => {
+                write!(f, "cannot read the day number of entry {:?}", entry)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegisterError {}
+
+/// Adds day `day` to the `DAYS` registry in `src/solution.rs`, keeping it in
+/// day order.
+pub fn register_day(solution: &str, day: u8) -> std::result::Result<String, RegisterError> {
+    let start = solution
+        .find("static DAYS: &[Day] = &[")
+        .ok_or(RegisterError::MissingRegistry)?;
+    let end = start
+        + solution[start..]
+            .find("\n];")
+            .ok_or(RegisterError::MissingRegistry)?;
+    let entry = format!(
+        "    Day {{\n        number: {0},\n        solver: &crate::day{0}::Day{0},\n    }},\n",
+        day
+    );
+
+    let mut at = end + 1;
+    let mut offset = start;
+    while let Some(i) = solution[offset..end].find("    Day {\n") {
+        let entry_start = offset + i;
+        let number = solution[entry_start..end]
+            .split("number: ")
+            .nth(1)
+            .and_then(|rest| rest.split(',').next())
+            .and_then(|n| n.parse::<u8>().ok())
+            .ok_or_else(|| {
+                let entry = solution[entry_start..end].split("},").next().unwrap_or("");
+                RegisterError::UnreadableEntry(entry.trim().to_owned())
+            })?;
+        if number == day {
+            return Err(RegisterError::AlreadyRegistered);
+        }
+        if number > day {
+            at = entry_start;
+            break;
+        }
+        offset = entry_start + 1;
+    }
+
+    Ok(format!("{}{}{}", &solution[..at], entry, &solution[at..]))
+}
+
+/// Creates `src/day<N>/mod.rs` and an empty `src/day<N>/input.txt` under
+/// `root` and registers the day with the runner. Fails without touching
+/// anything if the module or a non-empty input already exists, or if the
+/// runner's registry can't be updated.
+pub fn create(root: &Path, day: u8) -> Result<Vec<PathBuf>> {
+    let src = root.join("src");
+    let dir = src.join(format!("day{}", day));
+    let module_path = dir.join("mod.rs");
+    let input_path = dir.join("input.txt");
+
+    for path in &[&module_path, &input_path] {
+        let exists = match fs::read_to_string(path) {
+            Ok(contents) => *path == &module_path || !contents.trim().is_empty(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => false,
+            Err(e) => return Err(e.into()),
+        };
+        if exists {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{}: already exists, not overwriting", path.display()),
+            )
+            .into());
+        }
+    }
+
+    let lib_path = src.join("lib.rs");
+    let solution_path = src.join("solution.rs");
+    let lib = declare_module(&fs::read_to_string(&lib_path)?, day);
+    let solution = match register_day(&fs::read_to_string(&solution_path)?, day) {
+        Ok(solution) => Some(solution),
+        Err(RegisterError::AlreadyRegistered) => None,
+        Err(e) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", solution_path.display(), e),
+            )
+            .into())
+        }
+    };
+
+    fs::create_dir_all(&dir)?;
+    fs::write(&module_path, module(day))?;
+    let mut written = vec![module_path];
+    if !input_path.exists() {
+        fs::write(&input_path, "")?;
+        written.push(input_path);
+    }
+    if let Some(lib) = lib {
+        fs::write(&lib_path, lib)?;
+        written.push(lib_path);
+    }
+    if let Some(solution) = solution {
+        fs::write(&solution_path, solution)?;
+        written.push(solution_path);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "pub mod error;
+
+pub mod day1;
+pub mod day12;
+pub mod day2;
+
+pub use error::Error;
+";
+
+    const SOLUTION: &str = "static DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        solver: &crate::day1::Day1,
+    },
+    Day {
+        number: 12,
+        solver: &crate::day12::Day12,
+    },
+];
+";
+
+    #[test]
+    fn test_module() {
+        let module = module(13);
+        assert!(module.contains("pub struct Day13;"));
+        assert!(module.contains("impl Solution for Day13 {"));
+        assert!(module.contains("fn test_part2()"));
+    }
+
+    #[test]
+    fn test_declare_module() {
+        let lib = declare_module(LIB, 13).unwrap();
+        assert!(lib.contains("pub mod day12;\npub mod day13;\npub mod day2;\n"));
+
+        let lib = declare_module(LIB, 3).unwrap();
+        assert!(lib.contains("pub mod day2;\npub mod day3;\n\npub use"));
+
+        assert_eq!(declare_module(LIB, 12), None);
+    }
+
+    #[test]
+    fn test_register_day() {
+        let solution = register_day(SOLUTION, 5).unwrap();
+        assert!(solution.contains(
+            "solver: &crate::day1::Day1,
+    },
+    Day {
+        number: 5,
+        solver: &crate::day5::Day5,
+    },
+    Day {
+        number: 12,"
+        ));
+
+        let solution = register_day(SOLUTION, 25).unwrap();
+        assert!(solution.ends_with(
+            "        solver: &crate::day25::Day25,
+    },
+];
+"
+        ));
+
+        assert_eq!(
+            register_day(SOLUTION, 12),
+            Err(RegisterError::AlreadyRegistered)
+        );
+        assert_eq!(
+            register_day("static DAYS: Vec<Day> = vec![];", 5),
+            Err(RegisterError::MissingRegistry)
+        );
+        assert_eq!(
+            register_day(&SOLUTION.replace("number: 12", "number: DAY"), 13),
+            Err(RegisterError::UnreadableEntry(
+                "Day {\n        number: DAY,\n        solver: &crate::day12::Day12,".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_create_refuses_to_overwrite() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let dir = root.join("src/day7");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        fs::write(root.join("src/solution.rs"), SOLUTION).unwrap();
+
+        fs::write(
+            dir.join("input.txt"),
+            "light red bags contain 1 bright white bag.",
+        )
+        .unwrap();
+        assert!(create(&root, 7).is_err());
+
+        fs::write(dir.join("input.txt"), "").unwrap();
+        let written = create(&root, 7).unwrap();
+        assert_eq!(written.len(), 3);
+        assert!(fs::read_to_string(root.join("src/lib.rs"))
+            .unwrap()
+            .contains("pub mod day7;"));
+        assert!(create(&root, 7).is_err());
+
+        // Nothing is written when the day can't be registered.
+        fs::write(root.join("src/solution.rs"), "").unwrap();
+        let error = create(&root, 8).unwrap_err().to_string();
+        assert!(
+            error.ends_with("solution.rs: no `static DAYS: &[Day]` registry found"),
+            "{}",
+            error
+        );
+        assert!(!root.join("src/day8").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,258 @@
+use crate::bench::Stage;
+use crate::Result;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Which half of a day's puzzle to answer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+}
+
+/// A day's puzzle, split into parsing the input text and answering each part from it.
+pub trait Solution {
+    /// The parsed puzzle input. It may borrow from the text it was parsed from.
+    type Input<'a>;
+
+    fn parse<'a>(&self, text: &'a str) -> Result<Self::Input<'a>>;
+
+    /// Returns `None` when there is no answer for this input.
+    fn part1(&self, input: &Self::Input<'_>) -> Option<String>;
+
+    /// Returns `None` when there is no answer for this input.
+    fn part2(&self, input: &Self::Input<'_>) -> Option<String>;
+}
+
+/// Object-safe view of a `Solution` so every day can live in one registry.
+pub trait Solver: Sync {
+    fn solve(&self, text: &str, part: Part) -> Result<Option<String>>;
+
+    /// Times parsing and then each of `parts` on their own, `iterations`
+    /// times each, returning every run's wall time per stage.
+    fn bench(
+        &self,
+        text: &str,
+        parts: &[Part],
+        iterations: usize,
+    ) -> Result<Vec<(Stage, Vec<Duration>)>>;
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn solve(&self, text: &str, part: Part) -> Result<Option<String>> {
+        let input = self.parse(text)?;
+        Ok(match part {
+            Part::One => self.part1(&input),
+            Part::Two => self.part2(&input),
+        })
+    }
+
+    fn bench(
+        &self,
+        text: &str,
+        parts: &[Part],
+        iterations: usize,
+    ) -> Result<Vec<(Stage, Vec<Duration>)>> {
+        let input = self.parse(text)?;
+        let time = |f: &dyn Fn()| -> Vec<Duration> {
+            (0..iterations)
+                .map(|_| {
+                    let start = Instant::now();
+                    f();
+                    start.elapsed()
+                })
+                .collect()
+        };
+
+        let mut stages = vec![(
+            Stage::Parse,
+            time(&|| {
+                black_box(self.parse(black_box(text)).ok());
+            }),
+        )];
+        for &part in parts {
+            let samples = time(&|| {
+                black_box(match part {
+                    Part::One => self.part1(black_box(&input)),
+                    Part::Two => self.part2(black_box(&input)),
+                });
+            });
+            stages.push((Stage::Part(part), samples));
+        }
+
+        Ok(stages)
+    }
+}
+
+/// A registered day and the solver for it.
+pub struct Day {
+    pub number: u8,
+    pub solver: &'static dyn Solver,
+}
+
+static DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        solver: &crate::day1::Day1,
+    },
+    Day {
+        number: 2,
+        solver: &crate::day2::Day2,
+    },
+    Day {
+        number: 3,
+        solver: &crate::day3::Day3,
+    },
+    Day {
+        number: 4,
+        solver: &crate::day4::Day4,
+    },
+    Day {
+        number: 5,
+        solver: &crate::day5::Day5,
+    },
+    Day {
+        number: 6,
+        solver: &crate::day6::Day6,
+    },
+    Day {
+        number: 7,
+        solver: &crate::day7::Day7,
+    },
+    Day {
+        number: 8,
+        solver: &crate::day8::Day8,
+    },
+    Day {
+        number: 9,
+        solver: &crate::day9::Day9,
+    },
+    Day {
+        number: 10,
+        solver: &crate::day10::Day10,
+    },
+    Day {
+        number: 11,
+        solver: &crate::day11::Day11,
+    },
+    Day {
+        number: 12,
+        solver: &crate::day12::Day12,
+    },
+    Day {
+        number: 13,
+        solver: &crate::day13::Day13,
+    },
+    Day {
+        number: 14,
+        solver: &crate::day14::Day14,
+    },
+    Day {
+        number: 15,
+        solver: &crate::day15::Day15,
+    },
+    Day {
+        number: 16,
+        solver: &crate::day16::Day16,
+    },
+    Day {
+        number: 17,
+        solver: &crate::day17::Day17,
+    },
+    Day {
+        number: 18,
+        solver: &crate::day18::Day18,
+    },
+    Day {
+        number: 19,
+        solver: &crate::day19::Day19,
+    },
+    Day {
+        number: 20,
+        solver: &crate::day20::Day20,
+    },
+    Day {
+        number: 21,
+        solver: &crate::day21::Day21,
+    },
+    Day {
+        number: 22,
+        solver: &crate::day22::Day22,
+    },
+    Day {
+        number: 23,
+        solver: &crate::day23::Day23,
+    },
+    Day {
+        number: 24,
+        solver: &crate::day24::Day24,
+    },
+    Day {
+        number: 25,
+        solver: &crate::day25::Day25,
+    },
+];
+
+/// Every day with a solver, in day order.
+pub fn days() -> &'static [Day] {
+    DAYS
+}
+
+pub fn day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_in_order() {
+        assert!(days().windows(2).all(|w| w[0].number < w[1].number));
+    }
+
+    #[test]
+    fn test_day() {
+        assert_eq!(day(1).map(|d| d.number), Some(1));
+        assert!(day(26).is_none());
+    }
+
+    #[test]
+    fn test_solve() {
+        let day = day(1).unwrap();
+        let text = "1721\n979\n366\n299\n675\n1456";
+        assert_eq!(
+            day.solver.solve(text, Part::One).unwrap(),
+            Some("514579".to_owned())
+        );
+        assert_eq!(
+            day.solver.solve(text, Part::Two).unwrap(),
+            Some("241861950".to_owned())
+        );
+        assert!(day.solver.solve("1721\nx", Part::One).is_err());
+    }
+
+    #[test]
+    fn test_bench() {
+        let day = day(1).unwrap();
+        let stages = day
+            .solver
+            .bench("1721\n979\n366\n299\n675\n1456", &[Part::Two], 3)
+            .unwrap();
+        let names: Vec<String> = stages.iter().map(|(s, _)| s.to_string()).collect();
+        assert_eq!(names, ["parse", "part2"]);
+        assert!(stages.iter().all(|(_, samples)| samples.len() == 3));
+    }
+}
@@ -0,0 +1,237 @@
+use crate::store::InputStore;
+use crate::{Error, Result};
+
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming a directory laid out like `src/`, i.e. holding
+/// `day<N>/input.txt` for each day.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a day's puzzle input is read from.
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Self::Path(path) => read_input(path),
+            Self::Stdin => {
+                let mut contents = String::new();
+                io::stdin().read_to_string(&mut contents)?;
+                Ok(contents)
+            }
+        }
+    }
+}
+
+/// Reads a puzzle input file, treating a missing or blank file as an error
+/// rather than handing an empty input to a solver.
+pub fn read_input(path: &Path) -> Result<String> {
+    let contents = fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => Error::MissingInput(path.to_owned()),
+        _ => io::Error::new(e.kind(), format!("{}: {}", path.display(), e)).into(),
+    })?;
+
+    if contents.trim().is_empty() {
+        return Err(Error::EmptyInput(path.to_owned()));
+    }
+
+    Ok(contents)
+}
+
+impl std::fmt::Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Decides where each day's input comes from, in order of preference:
+///
+/// 1. an explicit path, or `-` for stdin
+/// 2. `$AOC_INPUT_DIR/day<N>/input.txt`
+/// 3. the input store, `$AOC_CACHE_DIR/<year>/<N>/<profile>.txt`
+/// 4. `src/day<N>/input.txt` under the directory this crate was built from
+///
+/// A configured directory or store that lacks the day's input is skipped. If
+/// no candidate has the file, the most preferred one is returned so reading it
+/// reports what is missing.
+#[derive(Debug, Default)]
+pub struct Resolver {
+    explicit: Option<String>,
+    input_dir: Option<PathBuf>,
+    store: Option<InputStore>,
+}
+
+impl Resolver {
+    pub fn new(input_dir: Option<PathBuf>) -> Self {
+        Self {
+            explicit: None,
+            input_dir,
+            store: None,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let mut resolver = Self::new(env::var_os(INPUT_DIR_VAR).map(PathBuf::from));
+        resolver.store = InputStore::from_env();
+        resolver
+    }
+
+    pub fn with_input(mut self, path: impl Into<String>) -> Self {
+        self.explicit = Some(path.into());
+        self
+    }
+
+    pub fn with_store(mut self, store: InputStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    pub fn store(&self) -> Option<&InputStore> {
+        self.store.as_ref()
+    }
+
+    pub fn resolve(&self, day: u8) -> InputSource {
+        match self.explicit.as_deref() {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::Path(PathBuf::from(path)),
+            None => {
+                let candidates: Vec<PathBuf> = self
+                    .input_dir
+                    .iter()
+                    .map(|dir| day_input(dir, day))
+                    .chain(self.store.iter().map(|store| store.path(crate::YEAR, day)))
+                    .chain(std::iter::once(day_input(
+                        &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+                        day,
+                    )))
+                    .collect();
+                let path = candidates
+                    .iter()
+                    .find(|path| path.is_file())
+                    .unwrap_or(&candidates[0]);
+                InputSource::Path(path.clone())
+            }
+        }
+    }
+}
+
+fn day_input(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{}", day)).join("input.txt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_explicit() {
+        let resolver = Resolver::new(Some(PathBuf::from("/inputs"))).with_input("mine.txt");
+        assert_eq!(
+            resolver.resolve(3),
+            InputSource::Path(PathBuf::from("mine.txt"))
+        );
+
+        let resolver = Resolver::new(None).with_input("-");
+        assert_eq!(resolver.resolve(3), InputSource::Stdin);
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn manifest_input(day: u8) -> InputSource {
+        InputSource::Path(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("src/day{}/input.txt", day)),
+        )
+    }
+
+    #[test]
+    fn test_resolve_input_dir() {
+        let dir = temp_dir("input-dir");
+        fs::create_dir_all(dir.join("day3")).unwrap();
+        fs::write(dir.join("day3/input.txt"), "1\n").unwrap();
+        let resolver = Resolver::new(Some(dir.clone()));
+        let day3 = resolver.resolve(3);
+        let day1 = resolver.resolve(1);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(day3, InputSource::Path(dir.join("day3/input.txt")));
+        // day1 is not in the directory, so it falls back to the crate root
+        assert_eq!(day1, manifest_input(1));
+    }
+
+    #[test]
+    fn test_resolve_store() {
+        let cache = temp_dir("resolve-store");
+        let store = InputStore::new(&cache, "alice");
+        fs::create_dir_all(cache.join("2020/3")).unwrap();
+        fs::write(cache.join("2020/3/alice.txt"), "1\n").unwrap();
+
+        let resolver = Resolver::new(None).with_store(store.clone());
+        let day3 = resolver.resolve(3);
+        let day1 = resolver.resolve(1);
+        let resolver = Resolver::new(Some(PathBuf::from("/no/such/inputs"))).with_store(store);
+        let skipping_dir = resolver.resolve(3);
+        fs::remove_dir_all(&cache).unwrap();
+
+        assert_eq!(day3, InputSource::Path(cache.join("2020/3/alice.txt")));
+        assert_eq!(day1, manifest_input(1));
+        assert_eq!(
+            skipping_dir,
+            InputSource::Path(cache.join("2020/3/alice.txt"))
+        );
+    }
+
+    #[test]
+    fn test_resolve_nowhere() {
+        // no candidate has day 26, so the first one is named
+        let resolver = Resolver::new(Some(PathBuf::from("/inputs")));
+        assert_eq!(
+            resolver.resolve(26),
+            InputSource::Path(PathBuf::from("/inputs/day26/input.txt"))
+        );
+        assert_eq!(Resolver::new(None).resolve(26), manifest_input(26));
+    }
+
+    #[test]
+    fn test_resolve_manifest_dir() {
+        let resolver = Resolver::new(None);
+        let source = resolver.resolve(1);
+        assert_eq!(source, manifest_input(1));
+        assert!(source.read().is_ok());
+    }
+
+    #[test]
+    fn test_read_missing_names_path() {
+        let source = InputSource::Path(PathBuf::from("/no/such/input.txt"));
+        assert_eq!(
+            source.read().unwrap_err().to_string(),
+            "/no/such/input.txt: no input file"
+        );
+    }
+
+    #[test]
+    fn test_read_empty() {
+        let path = env::temp_dir().join(format!("aoc-empty-{}.txt", std::process::id()));
+        fs::write(&path, "\n").unwrap();
+        let e = InputSource::Path(path.clone()).read().unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            e.to_string(),
+            format!("{}: input file is empty", path.display())
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::source::read_input;
+use crate::Result;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the root of the input store.
+pub const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+
+/// Environment variable choosing which account's inputs to use.
+pub const PROFILE_VAR: &str = "AOC_PROFILE";
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Whether an input file is usable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Present,
+    /// The file exists but holds nothing but whitespace, like the
+    /// placeholders created by `aoc new`.
+    Empty,
+    Missing,
+}
+
+impl Status {
+    pub fn of(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(contents) if contents.trim().is_empty() => Self::Empty,
+            Ok(_) => Self::Present,
+            Err(_) => Self::Missing,
+        }
+    }
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Present => write!(f, "present"),
+            Self::Empty => write!(f, "empty"),
+            Self::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// Puzzle inputs kept on disk for several accounts and years, laid out as
+/// `<root>/<year>/<day>/<profile>.txt`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputStore {
+    root: PathBuf,
+    profile: String,
+}
+
+impl InputStore {
+    pub fn new(root: impl Into<PathBuf>, profile: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            profile: profile.into(),
+        }
+    }
+
+    /// The store under `$AOC_CACHE_DIR` for `$AOC_PROFILE`, if a root is set.
+    pub fn from_env() -> Option<Self> {
+        let root = env::var_os(CACHE_DIR_VAR)?;
+        let profile = env::var(PROFILE_VAR).unwrap_or_else(|_| DEFAULT_PROFILE.to_owned());
+        Some(Self::new(root, profile))
+    }
+
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = profile.into();
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(day.to_string())
+            .join(format!("{}.txt", self.profile))
+    }
+
+    pub fn status(&self, year: u16, day: u8) -> Status {
+        Status::of(&self.path(year, day))
+    }
+
+    /// Reads one day's input, failing with `Error::MissingInput` or
+    /// `Error::EmptyInput` instead of returning text no solver can parse.
+    pub fn load(&self, year: u16, day: u8) -> Result<String> {
+        read_input(&self.path(year, day))
+    }
+
+    /// The days of `year` that have a non-empty input for this profile.
+    pub fn available(&self, year: u16) -> Vec<u8> {
+        (1..=25)
+            .filter(|&day| self.status(year, day) == Status::Present)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    fn store(name: &str) -> InputStore {
+        let root = env::temp_dir().join(format!("aoc-store-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        InputStore::new(root, "alice")
+    }
+
+    fn write(store: &InputStore, year: u16, day: u8, contents: &str) {
+        let path = store.path(year, day);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_path() {
+        let store = InputStore::new("/cache", "alice");
+        assert_eq!(
+            store.path(2020, 7),
+            PathBuf::from("/cache/2020/7/alice.txt")
+        );
+        assert_eq!(
+            store.with_profile("bob").path(2019, 25),
+            PathBuf::from("/cache/2019/25/bob.txt")
+        );
+    }
+
+    #[test]
+    fn test_available() {
+        let store = store("available");
+        write(&store, 2020, 1, "1721\n979\n");
+        write(&store, 2020, 3, "\n\n");
+        write(&store, 2020, 12, "F10\n");
+        write(&store, 2019, 2, "1,0,0,3\n");
+        write(&store.clone().with_profile("bob"), 2020, 5, "FBFBBFFRLR\n");
+
+        assert_eq!(store.available(2020), vec![1, 12]);
+        assert_eq!(store.status(2020, 3), Status::Empty);
+        assert_eq!(store.status(2020, 5), Status::Missing);
+
+        fs::remove_dir_all(store.root()).unwrap();
+    }
+
+    #[test]
+    fn test_load() {
+        let store = store("load");
+        write(&store, 2020, 1, "1721\n979\n");
+        write(&store, 2020, 2, "  \n");
+
+        assert_eq!(store.load(2020, 1).unwrap(), "1721\n979\n");
+        match store.load(2020, 2) {
+            Err(Error::EmptyInput(path)) => assert_eq!(path, store.path(2020, 2)),
+            other => panic!("expected empty input, got {:?}", other),
+        }
+        match store.load(2020, 3) {
+            Err(Error::MissingInput(path)) => assert_eq!(path, store.path(2020, 3)),
+            other => panic!("expected missing input, got {:?}", other),
+        }
+
+        fs::remove_dir_all(store.root()).unwrap();
+    }
+}
@@ -0,0 +1,27 @@
+//! Helpers shared by tests across days.
+
+/// A linear congruential generator, for tests that want varied inputs that
+/// are still the same on every run.
+pub struct Lcg {
+    state: u64,
+}
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// A number in `0..2^31`.
+    pub fn next_u31(&mut self) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        self.state >> 33
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u31() % n as u64) as usize
+    }
+}